@@ -0,0 +1,109 @@
+//! Recorded answers, used to catch regressions when a solved day is refactored.
+//!
+//! Every year has its own file, holding one `<day> <part> <answer>` entry per line, e.g.
+//! `e5 2 46`. Blank lines and lines starting with `#` are ignored.
+
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+
+use crate::error::{Error, Result};
+
+/// Default location of the recorded answers of `year`.
+pub fn default_path(year: u32) -> String {
+    format!("{}/answers.txt", year)
+}
+
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Answers {
+    entries: BTreeMap<(u32, u8), String>,
+}
+
+impl Answers {
+    pub fn parse(source: &str) -> Result<Answers> {
+        let mut entries = BTreeMap::new();
+        for (idx, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: &str| Error::parse(1, line, message).on_line(idx + 1);
+            let mut sp = line.splitn(3, ' ');
+            let day = sp
+                .next()
+                .and_then(|d| d.strip_prefix('e'))
+                .and_then(|d| d.parse::<u32>().ok())
+                .ok_or(error("expected a day like `e5`"))?;
+            let part = match sp.next() {
+                Some("1") => 1,
+                Some("2") => 2,
+                _ => return Err(error("expected part 1 or 2")),
+            };
+            let answer = match sp.next().map(|a| a.trim()) {
+                Some(answer) if !answer.is_empty() => answer.to_string(),
+                _ => return Err(error("missing answer")),
+            };
+            if entries.insert((day, part), answer).is_some() {
+                return Err(error("duplicate entry"));
+            }
+        }
+        Ok(Answers { entries })
+    }
+
+    pub fn load(path: &str) -> Result<Answers> {
+        let source = read_to_string(path).map_err(|err| Error::io(path, err))?;
+        Answers::parse(&source).map_err(|err| err.in_file(path))
+    }
+
+    pub fn get(&self, day: u32, part: u8) -> Option<&str> {
+        self.entries.get(&(day, part)).map(|a| a.as_str())
+    }
+
+    pub fn has_day(&self, day: u32) -> bool {
+        self.get(day, 1).is_some() || self.get(day, 2).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+
+    use super::Answers;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse(
+            "# day part answer
+e1 1 142
+
+e5 2 46
+",
+        )
+        .unwrap();
+        assert_eq!(answers.get(1, 1), Some("142"));
+        assert_eq!(answers.get(1, 2), None);
+        assert_eq!(answers.get(5, 2), Some("46"));
+        assert!(answers.has_day(5));
+        assert!(!answers.has_day(2));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Answers::parse("5 1 12"),
+            Err(Error::parse(1, "5 1 12", "expected a day like `e5`").on_line(1))
+        );
+        assert_eq!(
+            Answers::parse("e5 3 12"),
+            Err(Error::parse(1, "e5 3 12", "expected part 1 or 2").on_line(1))
+        );
+        assert_eq!(
+            Answers::parse("e5 1"),
+            Err(Error::parse(1, "e5 1", "missing answer").on_line(1))
+        );
+        assert_eq!(
+            Answers::parse("e5 1 12\n\ne5 1 13")
+                .map_err(|err| err.in_file("answers.txt").to_string()),
+            Err("answers.txt:3:1: duplicate entry".to_string())
+        );
+    }
+}
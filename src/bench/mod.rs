@@ -0,0 +1,322 @@
+//! Repeated timing of a day, to measure its phases and catch performance regressions.
+//!
+//! A baseline file holds one `<day> <phase> <median in ns>` entry per line, e.g.
+//! `e5 part2 1234567`. Blank lines and lines starting with `#` are ignored.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::time::Duration;
+
+use crate::error::{Error, Result};
+use crate::runner::{self, format_duration, format_table};
+use crate::solution::{Day, Parts};
+
+pub const DEFAULT_RUNS: usize = 10;
+/// Slowdown of the median, in percent, above which a phase is reported as a regression.
+pub const DEFAULT_THRESHOLD: u32 = 10;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = match n % 2 {
+            0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+            _ => sorted[n / 2],
+        };
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        let variance = match n {
+            1 => 0.0,
+            _ => {
+                sorted
+                    .iter()
+                    .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+                    .sum::<f64>()
+                    / (n - 1) as f64
+            }
+        };
+        Stats {
+            min: sorted[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Timings of one phase of a day: `parse`, `part1`, `part2`, or `total` for whole runs.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Measure {
+    pub phase: String,
+    pub stats: Stats,
+}
+
+/// Parses and solves `day` `runs` times over, timing every phase.
+pub fn bench(
+    day: &Day,
+    path: &str,
+    source: &str,
+    parts: Parts,
+    runs: usize,
+) -> Result<Vec<Measure>> {
+    let mut samples: Vec<(String, Vec<Duration>)> = vec![];
+    for _ in 0..runs {
+        let report = runner::solve(day, path, source, parts)?;
+        let mut phases = vec![("parse".to_string(), report.parse_time)];
+        phases.extend(
+            report
+                .parts
+                .iter()
+                .map(|part| (format!("part{}", part.part), part.time)),
+        );
+        let total = phases.iter().map(|(_, time)| *time).sum();
+        phases.push(("total".to_string(), total));
+
+        for (idx, (phase, time)) in phases.into_iter().enumerate() {
+            match samples.get_mut(idx) {
+                Some((_, times)) => times.push(time),
+                None => samples.push((phase, vec![time])),
+            }
+        }
+    }
+    Ok(samples
+        .into_iter()
+        .map(|(phase, times)| Measure {
+            phase,
+            stats: Stats::from_samples(&times),
+        })
+        .collect())
+}
+
+/// Median timings of a previous run, by day and phase.
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Baseline {
+    entries: BTreeMap<(u32, String), Duration>,
+}
+
+impl Baseline {
+    pub fn parse(source: &str) -> Result<Baseline> {
+        let mut entries = BTreeMap::new();
+        for (idx, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: &str| Error::parse(1, line, message).on_line(idx + 1);
+            let fields = line.split_whitespace().collect::<Vec<&str>>();
+            let [day, phase, nanos] = fields[..] else {
+                return Err(error("expected `<day> <phase> <median in ns>`"));
+            };
+            let day = day
+                .strip_prefix('e')
+                .and_then(|d| d.parse::<u32>().ok())
+                .ok_or(error("expected a day like `e5`"))?;
+            let nanos = nanos
+                .parse::<u64>()
+                .map_err(|_| Error::parse_in(line, nanos, "expected a duration in ns"))
+                .map_err(|err| err.on_line(idx + 1))?;
+            if entries
+                .insert((day, phase.to_string()), Duration::from_nanos(nanos))
+                .is_some()
+            {
+                return Err(error("duplicate entry"));
+            }
+        }
+        Ok(Baseline { entries })
+    }
+
+    pub fn load(path: &str) -> Result<Baseline> {
+        let source = fs::read_to_string(path).map_err(|err| Error::io(path, err))?;
+        Baseline::parse(&source).map_err(|err| err.in_file(path))
+    }
+
+    pub fn get(&self, day: u32, phase: &str) -> Option<Duration> {
+        self.entries.get(&(day, phase.to_string())).copied()
+    }
+
+    /// Replaces the entries of `day` with the medians of `measures`.
+    pub fn record(&mut self, day: u32, measures: &[Measure]) {
+        self.entries.retain(|(d, _), _| *d != day);
+        for measure in measures {
+            self.entries
+                .insert((day, measure.phase.clone()), measure.stats.median);
+        }
+    }
+
+    pub fn render(&self) -> String {
+        self.entries
+            .iter()
+            .map(|((day, phase), median)| format!("e{} {} {}\n", day, phase, median.as_nanos()))
+            .collect()
+    }
+}
+
+/// Records `measures` of `day` in the baseline at `path`, keeping the entries of other days.
+pub fn save(path: &str, day: u32, measures: &[Measure]) -> Result<()> {
+    let mut baseline = match Baseline::load(path) {
+        Err(Error::Io { source, .. }) if source.kind() == ErrorKind::NotFound => {
+            Baseline::default()
+        }
+        other => other?,
+    };
+    baseline.record(day, measures);
+    fs::write(path, baseline.render()).map_err(|err| Error::io(path, err))
+}
+
+/// Relative change of `current` over `baseline`, in percent.
+pub fn change(baseline: Duration, current: Duration) -> f64 {
+    (current.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0
+}
+
+/// Lays out `measures` in a table, compared with `baseline` if any. Also returns whether a
+/// median got slower than the baseline by more than `threshold` percent.
+pub fn format_report(
+    day: &Day,
+    measures: &[Measure],
+    baseline: Option<&Baseline>,
+    threshold: u32,
+) -> (String, bool) {
+    let mut header = vec!["Phase", "Min", "Median", "Mean", "Stddev"];
+    if baseline.is_some() {
+        header.extend(["Baseline", "Change"]);
+    }
+    let mut rows = vec![header.iter().map(|h| h.to_string()).collect::<Vec<_>>()];
+    let mut regressed = false;
+
+    for measure in measures {
+        let stats = &measure.stats;
+        let mut row = vec![
+            measure.phase.clone(),
+            format_duration(stats.min),
+            format_duration(stats.median),
+            format_duration(stats.mean),
+            format_duration(stats.stddev),
+        ];
+        if let Some(baseline) = baseline {
+            match baseline.get(day.number, &measure.phase) {
+                Some(previous) => {
+                    let change = change(previous, stats.median);
+                    let flag = match change > threshold as f64 {
+                        true => {
+                            regressed = true;
+                            " REGRESSION"
+                        }
+                        false => "",
+                    };
+                    row.push(format_duration(previous));
+                    row.push(format!("{:+.1}%{}", change, flag));
+                }
+                None => row.extend([String::new(), "new".to_string()]),
+            }
+        }
+        rows.push(row);
+    }
+
+    let mut right = vec![false, true, true, true, true];
+    if baseline.is_some() {
+        right.extend([true, false]);
+    }
+    (format_table(&rows, &right), regressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::error::Error;
+    use crate::y2023::DAYS;
+
+    use super::{format_report, Baseline, Measure, Stats};
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn statistics() {
+        assert_eq!(
+            Stats::from_samples(&[ms(4), ms(2), ms(6), ms(8)]),
+            Stats {
+                min: ms(2),
+                median: ms(5),
+                mean: ms(5),
+                stddev: Duration::from_secs_f64((20.0f64 / 3.0).sqrt() / 1000.0),
+            }
+        );
+        assert_eq!(
+            Stats::from_samples(&[ms(3)]),
+            Stats {
+                min: ms(3),
+                median: ms(3),
+                mean: ms(3),
+                stddev: Duration::ZERO,
+            }
+        );
+    }
+
+    #[test]
+    fn baseline_file() {
+        let mut baseline =
+            Baseline::parse("# day phase median\ne5 parse 1000\ne4 part1 20\n").unwrap();
+        assert_eq!(baseline.get(5, "parse"), Some(Duration::from_nanos(1000)));
+        assert_eq!(baseline.get(5, "part1"), None);
+
+        let stats = Stats::from_samples(&[Duration::from_nanos(300)]);
+        baseline.record(
+            5,
+            &[Measure {
+                phase: "part1".to_string(),
+                stats,
+            }],
+        );
+        assert_eq!(baseline.render(), "e4 part1 20\ne5 part1 300\n");
+        assert_eq!(Baseline::parse(&baseline.render()), Ok(baseline));
+
+        assert_eq!(
+            Baseline::parse("e5 parse"),
+            Err(Error::parse(1, "e5 parse", "expected `<day> <phase> <median in ns>`").on_line(1))
+        );
+        assert_eq!(
+            Baseline::parse("\ne5 parse 1.5ms"),
+            Err(Error::parse(10, "1.5ms", "expected a duration in ns").on_line(2))
+        );
+    }
+
+    #[test]
+    fn flag_regressions() {
+        let measure = |phase: &str, millis: u64| Measure {
+            phase: phase.to_string(),
+            stats: Stats::from_samples(&[ms(millis)]),
+        };
+        let measures = [
+            measure("parse", 1),
+            measure("part1", 12),
+            measure("total", 13),
+        ];
+        let baseline =
+            Baseline::parse("e1 parse 1000000\ne1 part1 10000000\ne1 total 11000000").unwrap();
+
+        let (table, regressed) = format_report(&DAYS[0], &measures, Some(&baseline), 19);
+        assert!(regressed);
+        assert_eq!(
+            table,
+            "Phase       Min    Median      Mean   Stddev  Baseline  Change
+-------------------------------------------------------------------------
+parse   1.000ms   1.000ms   1.000ms  0.000ms   1.000ms  +0.0%
+part1  12.000ms  12.000ms  12.000ms  0.000ms  10.000ms  +20.0% REGRESSION
+-------------------------------------------------------------------------
+total  13.000ms  13.000ms  13.000ms  0.000ms  11.000ms  +18.2%"
+        );
+        assert!(!format_report(&DAYS[0], &measures, Some(&baseline), 25).1);
+        assert!(!format_report(&DAYS[0], &measures, None, 10).1);
+    }
+}
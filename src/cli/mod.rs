@@ -0,0 +1,727 @@
+use crate::answers;
+use crate::bench::{DEFAULT_RUNS, DEFAULT_THRESHOLD};
+use crate::config::Config;
+use crate::runner::Format;
+use crate::serve::{DEFAULT_ADDRESS, DEFAULT_MAX_BODY};
+use crate::solution::Parts;
+use crate::trace::Level;
+
+pub const USAGE: &str =
+    "Usage: aoc2023 <day> [--part <1|2>] [--input <path>] [--format <text|json>]
+       aoc2023 all [--part <1|2>] [--format <text|json>] [--jobs <n>]
+       aoc2023 check [--answers <path>]
+       aoc2023 bench <day> [--part <1|2>] [--input <path>] [--runs <n>]
+                     [--baseline <path>] [--threshold <percent>] [--save <path>]
+       aoc2023 watch <day> [--part <1|2>] [--input <path>]
+       aoc2023 repl <day> [--input <path>]
+       aoc2023 serve [--listen <address>] [--max-body <bytes>]
+       aoc2023 fetch <day>
+       aoc2023 submit <day> <1|2> [<answer>] [--input <path>]
+       aoc2023 new <day>
+
+Every command applies to the event given with `--year`, the latest one by default.
+Defaults are read from aoc.toml, in the current directory or else in ~/.config/aoc/,
+and overridden by the AOC_INPUTS_DIR, AOC_FIXTURES_DIR, AOC_YEAR, AOC_SESSION_FILE and
+AOC_FORMAT environment variables, then by the options below.
+
+Commands:
+  <day>                 Run one day, given as `e5`, `5` or `--day 5`
+  all                   Run every registered day and print a timing table, along with
+                        the heap usage when built with `--features memory`
+  check                 Run every day and compare with the recorded answers
+  bench <day>           Time repeated runs of a day, optionally comparing them with a
+                        baseline saved by an earlier run
+  watch <day>           Run a day again whenever its input or examples change
+  repl <day>            Load the input of a day once and explore it interactively, with
+                        the commands listed by `help`
+  serve                 Answer `POST /<year>/day/<day>?part=<1|2>` requests, with the
+                        puzzle input as body, with the results as JSON
+  fetch <day>           Download the puzzle input of a day, unless already there
+                        (session token from AOC_SESSION or the session file)
+  submit <day> <part>   Submit an answer, computed from the input unless given,
+                        and record the attempt in <year>/submissions.txt
+  new <day>             Create and register the module of a new day
+
+Options:
+  -c, --config <path>   Read the settings from <path> instead of aoc.toml
+      --inputs <dir>    Directory of the inputs, answers and submissions (default: .)
+      --fixtures <dir>  Directory of the examples (default: fixtures)
+  -v, --verbose         Time the parsing and each part on stderr
+      --trace           Also time the inner phases of the solutions, with their events
+      --trace-file <path>
+                        Write a Chrome trace of the run to <path>, for chrome://tracing
+  -y, --year <year>     Event of the day, e.g. 2023
+  -d, --day <day>       Day to run, instead of the <day> argument
+  -p, --part <1|2>      Only solve the given part (default: both)
+  -i, --input <path>    Read the puzzle input from <path>, or `-` for stdin
+                        (default: <inputs>/<year>/<day>_input.txt)
+  -f, --format <text|json>
+                        Print the results as text or as a JSON document
+                        (default: text)
+  -j, --jobs <n>        Number of days run at the same time by `all` (default: 1)
+      --answers <path>  Recorded answers file (default: <inputs>/<year>/answers.txt)
+      --runs <n>        Number of benchmark runs (default: 10)
+      --baseline <path> Compare the benchmark with the baseline in <path>
+      --threshold <percent>
+                        Slowdown reported as a regression (default: 10)
+      --save <path>     Record the benchmark as the baseline of the day in <path>
+      --listen <address>
+                        Address to serve on (default: 127.0.0.1:2023)
+      --max-body <bytes>
+                        Largest input accepted by `serve` (default: 1048576)
+  -h, --help            Print this help";
+
+/// Options without a value, as (long name, short name).
+const FLAGS: &[(&str, Option<&str>)] = &[("--verbose", Some("-v")), ("--trace", None)];
+
+/// Options taking a value, as (long name, short name).
+const OPTIONS: &[(&str, Option<&str>)] = &[
+    ("--config", Some("-c")),
+    ("--inputs", None),
+    ("--fixtures", None),
+    ("--trace-file", None),
+    ("--year", Some("-y")),
+    ("--day", Some("-d")),
+    ("--part", Some("-p")),
+    ("--input", Some("-i")),
+    ("--format", Some("-f")),
+    ("--jobs", Some("-j")),
+    ("--answers", None),
+    ("--runs", None),
+    ("--baseline", None),
+    ("--threshold", None),
+    ("--save", None),
+    ("--listen", None),
+    ("--max-body", None),
+];
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct RunOptions {
+    pub year: u32,
+    pub day: u32,
+    pub parts: Parts,
+    pub input: Option<String>,
+    pub format: Format,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct BenchOptions {
+    pub year: u32,
+    pub day: u32,
+    pub parts: Parts,
+    pub input: Option<String>,
+    pub runs: usize,
+    pub baseline: Option<String>,
+    /// Slowdown of a median over the baseline, in percent, reported as a regression.
+    pub threshold: u32,
+    pub save: Option<String>,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Command {
+    Help,
+    Run(RunOptions),
+    All {
+        year: u32,
+        parts: Parts,
+        format: Format,
+        /// Number of days run concurrently.
+        jobs: usize,
+    },
+    Check {
+        year: u32,
+        answers: String,
+    },
+    Bench(BenchOptions),
+    Watch {
+        year: u32,
+        day: u32,
+        parts: Parts,
+        input: Option<String>,
+    },
+    Repl {
+        year: u32,
+        day: u32,
+        input: Option<String>,
+    },
+    Serve {
+        address: String,
+        /// Largest request body accepted, in bytes.
+        max_body: usize,
+    },
+    Fetch {
+        year: u32,
+        day: u32,
+    },
+    Submit {
+        year: u32,
+        day: u32,
+        part: u8,
+        answer: Option<String>,
+        input: Option<String>,
+    },
+    New {
+        year: u32,
+        day: u32,
+    },
+}
+
+pub fn parse_day(source: &str) -> Result<u32, String> {
+    source
+        .strip_prefix('e')
+        .unwrap_or(source)
+        .parse::<u32>()
+        .map_err(|_| format!("invalid day `{}`, expected e.g. `e5` or `5`", source))
+}
+
+fn parse_part(source: Option<&str>) -> Result<Parts, String> {
+    match source {
+        None | Some("both") => Ok(Parts::Both),
+        Some("1") => Ok(Parts::One),
+        Some("2") => Ok(Parts::Two),
+        Some(other) => Err(format!("invalid part `{}`, expected 1, 2 or both", other)),
+    }
+}
+
+fn parse_format(source: Option<&str>, default: Format) -> Result<Format, String> {
+    match source {
+        None => Ok(default),
+        Some("text") => Ok(Format::Text),
+        Some("json") => Ok(Format::Json),
+        Some(other) => Err(format!("invalid format `{}`, expected text or json", other)),
+    }
+}
+
+/// Command line split into positional arguments and options, before it is interpreted.
+struct Args<'a> {
+    positionals: Vec<&'a str>,
+    options: Vec<(&'static str, &'a str)>,
+}
+
+impl<'a> Args<'a> {
+    fn split(args: &'a [String]) -> Result<Option<Args<'a>>, String> {
+        let mut positionals = vec![];
+        let mut options = vec![];
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-" => return Err("unexpected argument `-`".to_string()),
+                // Negative numbers, such as answers, are not options.
+                number if number.parse::<i64>().is_ok() => positionals.push(number),
+                flag if flag.starts_with('-') => {
+                    let matches = |(long, short): &&(&str, Option<&str>)| {
+                        *long == flag || *short == Some(flag)
+                    };
+                    if let Some((long, _)) = FLAGS.iter().find(matches) {
+                        options.push((*long, ""));
+                        continue;
+                    }
+                    let (long, _) = OPTIONS
+                        .iter()
+                        .find(matches)
+                        .ok_or(format!("unknown option `{}`", flag))?;
+                    let value = iter.next().ok_or(format!("missing value for `{}`", flag))?;
+                    options.push((*long, value.as_str()));
+                }
+                positional => positionals.push(positional),
+            }
+        }
+
+        Ok(Some(Args {
+            positionals,
+            options,
+        }))
+    }
+
+    fn take(&mut self, name: &str) -> Option<&'a str> {
+        let idx = self.options.iter().rposition(|(long, _)| *long == name)?;
+        let (_, value) = self.options.remove(idx);
+        self.options.retain(|(long, _)| *long != name);
+        Some(value)
+    }
+
+    /// Parses the day, given either as the positional argument at `idx` or with `--day`.
+    fn day(&mut self, idx: usize) -> Result<u32, String> {
+        if let Some(day) = self.take("--day") {
+            self.positionals
+                .insert(idx.min(self.positionals.len()), day);
+        }
+        parse_day(self.positionals.get(idx).ok_or("missing day argument")?)
+    }
+
+    /// Fails on any option or positional argument the command did not consume.
+    fn finish(self, command: &str, arity: usize) -> Result<(), String> {
+        if let Some(extra) = self.positionals.get(arity) {
+            return Err(format!("unexpected argument `{}`", extra));
+        }
+        match self.options.first() {
+            Some((long, _)) => Err(format!("`{}` cannot be used with `{}`", long, command)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Path given with `--config`, which has to be known before the other options are parsed.
+pub fn config_path(args: &[String]) -> Option<&str> {
+    args.windows(2)
+        .rev()
+        .find(|pair| pair[0] == "--config" || pair[0] == "-c")
+        .map(|pair| pair[1].as_str())
+}
+
+/// Parses the command line, applying the options overriding settings to `config`.
+pub fn parse_args(args: &[String], config: &mut Config) -> Result<Command, String> {
+    let mut args = match Args::split(args)? {
+        Some(args) => args,
+        None => return Ok(Command::Help),
+    };
+
+    args.take("--config");
+    for (option, key) in [
+        ("--inputs", "inputs_dir"),
+        ("--fixtures", "fixtures_dir"),
+        ("--year", "year"),
+        ("--trace-file", "trace_file"),
+    ] {
+        if let Some(value) = args.take(option) {
+            config.set(key, value)?;
+        }
+    }
+    if args.take("--verbose").is_some() {
+        config.trace = config.trace.max(Level::Verbose);
+    }
+    if args.take("--trace").is_some() {
+        config.trace = Level::Trace;
+    }
+    let year = config.year;
+    let command = args.positionals.first().copied().unwrap_or("<day>");
+    match command {
+        "all" => {
+            let parts = parse_part(args.take("--part"))?;
+            let format = parse_format(args.take("--format"), config.format)?;
+            let jobs = match args.take("--jobs") {
+                Some(jobs) => match jobs.parse::<usize>() {
+                    Ok(jobs) if jobs > 0 => jobs,
+                    _ => return Err(format!("invalid job count `{}`", jobs)),
+                },
+                None => 1,
+            };
+            args.finish(command, 1)?;
+            Ok(Command::All {
+                year,
+                parts,
+                format,
+                jobs,
+            })
+        }
+        "check" => {
+            let answers = match args.take("--answers") {
+                Some(path) => path.to_string(),
+                None => config.in_inputs(&answers::default_path(year)),
+            };
+            args.finish(command, 1)?;
+            Ok(Command::Check { year, answers })
+        }
+        "bench" => {
+            let day = args.day(1)?;
+            let parts = parse_part(args.take("--part"))?;
+            let input = args.take("--input").map(|s| s.to_string());
+            let runs = match args.take("--runs") {
+                Some(runs) => match runs.parse::<usize>() {
+                    Ok(runs) if runs > 0 => runs,
+                    _ => return Err(format!("invalid run count `{}`", runs)),
+                },
+                None => DEFAULT_RUNS,
+            };
+            let threshold = match args.take("--threshold") {
+                Some(threshold) => {
+                    threshold
+                        .trim_end_matches('%')
+                        .parse::<u32>()
+                        .map_err(|_| {
+                            format!("invalid threshold `{}`, expected a percentage", threshold)
+                        })?
+                }
+                None => DEFAULT_THRESHOLD,
+            };
+            let baseline = args.take("--baseline").map(|s| s.to_string());
+            let save = args.take("--save").map(|s| s.to_string());
+            args.finish(command, 2)?;
+            Ok(Command::Bench(BenchOptions {
+                year,
+                day,
+                parts,
+                input,
+                runs,
+                baseline,
+                threshold,
+                save,
+            }))
+        }
+        "watch" => {
+            let day = args.day(1)?;
+            let parts = parse_part(args.take("--part"))?;
+            let input = args.take("--input").map(|s| s.to_string());
+            args.finish(command, 2)?;
+            Ok(Command::Watch {
+                year,
+                day,
+                parts,
+                input,
+            })
+        }
+        "repl" => {
+            let day = args.day(1)?;
+            let input = args.take("--input").map(|s| s.to_string());
+            args.finish(command, 2)?;
+            Ok(Command::Repl { year, day, input })
+        }
+        "serve" => {
+            let address = args.take("--listen").unwrap_or(DEFAULT_ADDRESS).to_string();
+            let max_body = match args.take("--max-body") {
+                Some(size) => size
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid size `{}`, expected bytes", size))?,
+                None => DEFAULT_MAX_BODY,
+            };
+            args.finish(command, 1)?;
+            Ok(Command::Serve { address, max_body })
+        }
+        "fetch" => {
+            let day = args.day(1)?;
+            args.finish(command, 2)?;
+            Ok(Command::Fetch { year, day })
+        }
+        "new" => {
+            let day = args.day(1)?;
+            args.finish(command, 2)?;
+            Ok(Command::New { year, day })
+        }
+        "submit" => {
+            let day = args.day(1)?;
+            let part = match args.positionals.get(2) {
+                Some(&"1") => 1,
+                Some(&"2") => 2,
+                Some(other) => return Err(format!("invalid part `{}`, expected 1 or 2", other)),
+                None => return Err("missing part argument".to_string()),
+            };
+            let answer = args.positionals.get(3).map(|s| s.to_string());
+            let input = args.take("--input").map(|s| s.to_string());
+            args.finish(command, 4)?;
+            Ok(Command::Submit {
+                year,
+                day,
+                part,
+                answer,
+                input,
+            })
+        }
+        _ => {
+            let day = args.day(0)?;
+            let parts = parse_part(args.take("--part"))?;
+            let input = args.take("--input").map(|s| s.to_string());
+            let format = parse_format(args.take("--format"), config.format)?;
+            args.finish(command, 1)?;
+            Ok(Command::Run(RunOptions {
+                year,
+                day,
+                parts,
+                input,
+                format,
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Config;
+    use crate::runner::Format;
+    use crate::solution::Parts;
+    use crate::trace::Level;
+
+    use super::{config_path, BenchOptions, Command, RunOptions};
+
+    fn args(source: &str) -> Vec<String> {
+        source.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    fn parse_args(args: &[String]) -> Result<Command, String> {
+        super::parse_args(args, &mut Config::default())
+    }
+
+    #[test]
+    fn parse_run() {
+        assert_eq!(
+            parse_args(&args("e5")),
+            Ok(Command::Run(RunOptions {
+                year: 2023,
+                day: 5,
+                parts: Parts::Both,
+                input: None,
+                format: Format::Text
+            }))
+        );
+        assert_eq!(
+            parse_args(&args("3 --part 2 -i - --format json")),
+            Ok(Command::Run(RunOptions {
+                year: 2023,
+                day: 3,
+                parts: Parts::Two,
+                input: Some("-".to_string()),
+                format: Format::Json
+            }))
+        );
+        assert_eq!(parse_args(&args("e1 -h")), Ok(Command::Help));
+        assert_eq!(
+            parse_args(&args("all")),
+            Ok(Command::All {
+                year: 2023,
+                parts: Parts::Both,
+                format: Format::Text,
+                jobs: 1
+            })
+        );
+        assert_eq!(
+            parse_args(&args("-p 1 all -f json -j 4")),
+            Ok(Command::All {
+                year: 2023,
+                parts: Parts::One,
+                format: Format::Json,
+                jobs: 4
+            })
+        );
+        assert_eq!(
+            parse_args(&args("watch e3 -p 1")),
+            Ok(Command::Watch {
+                year: 2023,
+                day: 3,
+                parts: Parts::One,
+                input: None
+            })
+        );
+        assert_eq!(
+            parse_args(&args("repl e4 -i cards.txt")),
+            Ok(Command::Repl {
+                year: 2023,
+                day: 4,
+                input: Some("cards.txt".to_string())
+            })
+        );
+        assert_eq!(
+            parse_args(&args("serve --listen 0.0.0.0:8080 --max-body 4096")),
+            Ok(Command::Serve {
+                address: "0.0.0.0:8080".to_string(),
+                max_body: 4096
+            })
+        );
+        assert_eq!(
+            parse_args(&args("fetch e6")),
+            Ok(Command::Fetch { year: 2023, day: 6 })
+        );
+        assert_eq!(
+            parse_args(&args("new --year 2024 6")),
+            Ok(Command::New { year: 2024, day: 6 })
+        );
+        assert_eq!(
+            parse_args(&args("--year 2022 --day 5")),
+            Ok(Command::Run(RunOptions {
+                year: 2022,
+                day: 5,
+                parts: Parts::Both,
+                input: None,
+                format: Format::Text
+            }))
+        );
+        assert_eq!(
+            parse_args(&args("submit -y 2022 -d 5 2")),
+            Ok(Command::Submit {
+                year: 2022,
+                day: 5,
+                part: 2,
+                answer: None,
+                input: None
+            })
+        );
+        assert_eq!(
+            parse_args(&args("check")),
+            Ok(Command::Check {
+                year: 2023,
+                answers: "2023/answers.txt".to_string()
+            })
+        );
+        assert_eq!(
+            parse_args(&args("submit 5 2 46")),
+            Ok(Command::Submit {
+                year: 2023,
+                day: 5,
+                part: 2,
+                answer: Some("46".to_string()),
+                input: None
+            })
+        );
+        assert_eq!(
+            parse_args(&args("submit 5 2 -3")),
+            Ok(Command::Submit {
+                year: 2023,
+                day: 5,
+                part: 2,
+                answer: Some("-3".to_string()),
+                input: None
+            })
+        );
+        assert_eq!(
+            parse_args(&args("submit e5 1 -i -")),
+            Ok(Command::Submit {
+                year: 2023,
+                day: 5,
+                part: 1,
+                answer: None,
+                input: Some("-".to_string())
+            })
+        );
+        assert_eq!(
+            parse_args(&args(
+                "bench e4 -p 2 --runs 5 --threshold 15% --save bench.txt"
+            )),
+            Ok(Command::Bench(BenchOptions {
+                year: 2023,
+                day: 4,
+                parts: Parts::Two,
+                input: None,
+                runs: 5,
+                baseline: None,
+                threshold: 15,
+                save: Some("bench.txt".to_string())
+            }))
+        );
+        assert_eq!(
+            parse_args(&args("check --answers mine.txt")),
+            Ok(Command::Check {
+                year: 2023,
+                answers: "mine.txt".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn override_config() {
+        let mut config = Config {
+            inputs_dir: "inputs".to_string(),
+            format: Format::Json,
+            ..Config::default()
+        };
+        assert_eq!(
+            super::parse_args(&args("check -c aoc.toml --year 2022"), &mut config),
+            Ok(Command::Check {
+                year: 2022,
+                answers: "inputs/2022/answers.txt".to_string()
+            })
+        );
+        assert_eq!(config.year, 2022);
+        assert_eq!(
+            super::parse_args(&args("all --inputs . -f text"), &mut config),
+            Ok(Command::All {
+                year: 2022,
+                parts: Parts::Both,
+                format: Format::Text,
+                jobs: 1
+            })
+        );
+        assert_eq!(config.inputs_dir, ".");
+        super::parse_args(&args("e1 -v --trace-file trace.json"), &mut config).unwrap();
+        assert_eq!(config.trace, Level::Verbose);
+        assert_eq!(config.trace_file, Some("trace.json".to_string()));
+        super::parse_args(&args("--trace all -v"), &mut config).unwrap();
+        assert_eq!(config.trace, Level::Trace);
+        assert_eq!(
+            super::parse_args(&args("e1"), &mut config),
+            Ok(Command::Run(RunOptions {
+                year: 2022,
+                day: 1,
+                parts: Parts::Both,
+                input: None,
+                format: Format::Json
+            }))
+        );
+        assert_eq!(
+            config_path(&args("e1 --config a.toml -c b.toml")),
+            Some("b.toml")
+        );
+        assert_eq!(config_path(&args("e1 --config")), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            parse_args(&args("")),
+            Err("missing day argument".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("e1 e2")),
+            Err("unexpected argument `e2`".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("e1 --part 3")),
+            Err("invalid part `3`, expected 1, 2 or both".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("e1 --input")),
+            Err("missing value for `--input`".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("e1 --quiet")),
+            Err("unknown option `--quiet`".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("all -i 1_input.txt")),
+            Err("`--input` cannot be used with `all`".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("check e1")),
+            Err("unexpected argument `e1`".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("fetch")),
+            Err("missing day argument".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("e1 --format xml")),
+            Err("invalid format `xml`, expected text or json".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("all --jobs 0")),
+            Err("invalid job count `0`".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("bench e4 --runs 0")),
+            Err("invalid run count `0`".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("e4 --runs 3")),
+            Err("`--runs` cannot be used with `e4`".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("e5 --day 5")),
+            Err("unexpected argument `e5`".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("e5 --year last")),
+            Err("invalid year `last`, expected e.g. `2023`".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("all --day 5")),
+            Err("`--day` cannot be used with `all`".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("submit e5")),
+            Err("missing part argument".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("day5")),
+            Err("invalid day `day5`, expected e.g. `e5` or `5`".to_string())
+        );
+    }
+}
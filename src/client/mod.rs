@@ -0,0 +1,159 @@
+//! Access to adventofcode.com, authenticated with the session cookie of a logged in browser.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use ureq::Agent;
+
+use crate::config::config_dir;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/pluce/aoc2023";
+
+/// Where the session token is looked up by default when `AOC_SESSION` is not set.
+pub fn session_file() -> Option<PathBuf> {
+    Some(config_dir()?.join("aoc").join("session"))
+}
+
+/// Session token from the `AOC_SESSION` environment variable, or else from the session `file`.
+pub fn session_token(file: Option<&Path>) -> Result<String, String> {
+    if let Ok(token) = env::var("AOC_SESSION") {
+        return Ok(token.trim().to_string());
+    }
+    let path = file.ok_or("no session token: set AOC_SESSION".to_string())?;
+    match fs::read_to_string(path) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        _ => Err(format!(
+            "no session token: set AOC_SESSION or write it to `{}`",
+            path.display()
+        )),
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: Agent,
+}
+
+impl Client {
+    pub fn new(session: String) -> Client {
+        Client::with_base_url(BASE_URL, session)
+    }
+
+    pub fn with_base_url(base_url: &str, session: String) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        }
+    }
+
+    fn get(&self, path: &str) -> Result<String, String> {
+        let url = format!("{}{}", self.base_url, path);
+        match self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+        {
+            Ok(response) => response
+                .into_string()
+                .map_err(|err| format!("cannot read response from {}: {}", url, err)),
+            Err(ureq::Error::Status(400, _)) => Err(format!("{} rejected the session token", url)),
+            Err(ureq::Error::Status(404, _)) => Err(format!("{} is not available yet", url)),
+            Err(err) => Err(format!("cannot fetch {}: {}", url, err)),
+        }
+    }
+
+    pub fn input(&self, year: u32, day: u32) -> Result<String, String> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    /// Posts `answer` for a part of a day, returning the HTML page describing the verdict.
+    pub fn submit(&self, year: u32, day: u32, part: u8, answer: &str) -> Result<String, String> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        match self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+        {
+            Ok(response) => response
+                .into_string()
+                .map_err(|err| format!("cannot read response from {}: {}", url, err)),
+            Err(ureq::Error::Status(400, _)) => Err(format!("{} rejected the session token", url)),
+            Err(ureq::Error::Status(404, _)) => Err(format!("{} is not available yet", url)),
+            Err(err) => Err(format!("cannot post to {}: {}", url, err)),
+        }
+    }
+}
+
+/// Downloads the input of `day` into `path`, unless it is already there: inputs never change, so
+/// the file on disk is the cache. Returns whether a download happened.
+pub fn fetch_input(client: &Client, year: u32, day: u32, path: &Path) -> Result<bool, String> {
+    if path.exists() {
+        return Ok(false);
+    }
+    let input = client.input(year, day)?;
+    // Write next to the target then rename, so that an interrupted write is never mistaken for
+    // a cached input.
+    let partial = path.with_extension("part");
+    fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))
+        .and_then(|_| fs::write(&partial, input))
+        .and_then(|_| fs::rename(&partial, path))
+        .map_err(|err| format!("cannot write `{}`: {}", path.display(), err))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::tools::scratch_dir;
+    use crate::tools::stub::StubServer;
+
+    use super::{fetch_input, Client};
+
+    #[test]
+    fn fetch_once() {
+        let server = StubServer::start(|request| match request.path.as_str() {
+            "/2023/day/5/input" => (200, "seeds: 79 14 55 13\n".to_string()),
+            _ => (404, "Not found".to_string()),
+        });
+        let client = Client::with_base_url(&server.url, "s3cr3t".to_string());
+        let path = scratch_dir("fetch").join("2023/5_input.txt");
+
+        assert_eq!(fetch_input(&client, 2023, 5, &path), Ok(true));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "seeds: 79 14 55 13\n");
+        assert_eq!(fetch_input(&client, 2023, 5, &path), Ok(false));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].header("Cookie"), Some("session=s3cr3t"));
+        assert!(requests[0].header("User-Agent").is_some());
+    }
+
+    #[test]
+    fn fetch_errors_leave_no_cache() {
+        let server = StubServer::start(|request| match request.path.as_str() {
+            "/2023/day/6/input" => (400, "Please log in".to_string()),
+            _ => (404, "Not found".to_string()),
+        });
+        let client = Client::with_base_url(&server.url, "expired".to_string());
+        let dir = scratch_dir("fetch-errors");
+
+        let err = fetch_input(&client, 2023, 6, &dir.join("6_input.txt")).unwrap_err();
+        assert!(err.contains("rejected the session token"), "{}", err);
+        let err = fetch_input(&client, 2023, 25, &dir.join("25_input.txt")).unwrap_err();
+        assert!(err.contains("not available yet"), "{}", err);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+    }
+}
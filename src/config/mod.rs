@@ -0,0 +1,283 @@
+//! Project settings: where inputs and fixtures live, the default year and output format, and
+//! where the session token is stored.
+//!
+//! Settings come from the defaults, overridden by `aoc.toml` (in the current directory, or else
+//! in `~/.config/aoc/`), then by `AOC_*` environment variables, then by the command line. The file
+//! holds `key = value` lines, with quoted strings and integers as values:
+//!
+//! ```toml
+//! inputs_dir = "inputs"
+//! year = 2023
+//! format = "json"
+//! ```
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::fixtures::FIXTURES_DIR;
+use crate::runner::Format;
+use crate::solution::Day;
+use crate::trace::Level;
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// Settings, with the environment variable overriding each of them.
+const KEYS: &[(&str, &str)] = &[
+    ("inputs_dir", "AOC_INPUTS_DIR"),
+    ("fixtures_dir", "AOC_FIXTURES_DIR"),
+    ("year", "AOC_YEAR"),
+    ("session_file", "AOC_SESSION_FILE"),
+    ("format", "AOC_FORMAT"),
+    ("trace", "AOC_TRACE"),
+    ("trace_file", "AOC_TRACE_FILE"),
+];
+
+/// `$XDG_CONFIG_HOME`, or else `~/.config`.
+pub fn config_dir() -> Option<PathBuf> {
+    match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => Some(PathBuf::from(env::var_os("HOME")?).join(".config")),
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Config {
+    /// Directory holding one directory per year, with its inputs, answers and submissions.
+    pub inputs_dir: String,
+    pub fixtures_dir: String,
+    pub year: u32,
+    /// File holding the session token, used when `AOC_SESSION` is not set.
+    pub session_file: Option<PathBuf>,
+    pub format: Format,
+    /// Phases timed on stderr.
+    pub trace: Level,
+    /// Where to write a Chrome trace of the run.
+    pub trace_file: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            inputs_dir: ".".to_string(),
+            fixtures_dir: FIXTURES_DIR.to_string(),
+            year: crate::latest_year(),
+            session_file: crate::client::session_file(),
+            format: Format::Text,
+            trace: Level::Off,
+            trace_file: None,
+        }
+    }
+}
+
+/// Expands a leading `~` to the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+impl Config {
+    /// Changes the setting `key` to `value`, given as text.
+    pub fn set(&mut self, key: &str, value: &str) -> std::result::Result<(), String> {
+        match key {
+            "inputs_dir" => self.inputs_dir = value.to_string(),
+            "fixtures_dir" => self.fixtures_dir = value.to_string(),
+            "year" => {
+                self.year = value
+                    .parse()
+                    .map_err(|_| format!("invalid year `{}`, expected e.g. `2023`", value))?
+            }
+            "session_file" => self.session_file = Some(expand_home(value)),
+            "format" => {
+                self.format = match value {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    _ => return Err(format!("invalid format `{}`, expected text or json", value)),
+                }
+            }
+            "trace" => {
+                self.trace = match value {
+                    "off" => Level::Off,
+                    "verbose" => Level::Verbose,
+                    "trace" => Level::Trace,
+                    _ => {
+                        return Err(format!(
+                            "invalid trace level `{}`, expected off, verbose or trace",
+                            value
+                        ))
+                    }
+                }
+            }
+            "trace_file" => self.trace_file = Some(value.to_string()),
+            _ => return Err(format!("unknown setting `{}`", key)),
+        }
+        Ok(())
+    }
+
+    /// Applies the settings of a configuration file.
+    pub fn merge(&mut self, source: &str) -> Result<()> {
+        for (idx, line) in source.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let error =
+                |text: &str, message: String| Error::parse_in(line, text, message).on_line(idx + 1);
+            let (key, value) = trimmed
+                .split_once('=')
+                .ok_or(error(trimmed, "expected `<key> = <value>`".to_string()))?;
+            let (key, value) = (key.trim(), value.trim());
+            let text = match value.strip_prefix('"') {
+                Some(quoted) => quoted
+                    .strip_suffix('"')
+                    .filter(|text| !text.contains('"'))
+                    .ok_or(error(value, "expected a quoted string".to_string()))?,
+                None if value.chars().all(|c| c.is_ascii_digit()) && !value.is_empty() => value,
+                None => {
+                    return Err(error(
+                        value,
+                        "expected a quoted string or an integer".to_string(),
+                    ))
+                }
+            };
+            self.set(key, text)
+                .map_err(|message| match message.starts_with("unknown") {
+                    true => error(key, message),
+                    false => error(value, message),
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Applies the `AOC_*` variables found by `var`.
+    pub fn merge_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<()> {
+        for (key, name) in KEYS {
+            if let Some(value) = var(name) {
+                self.set(key, &value)
+                    .map_err(|message| Error::semantic(format!("{}: {}", name, message)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Settings from the defaults, the configuration file and the environment. The file is
+    /// `path` if given, or else the first of `aoc.toml` and `~/.config/aoc/aoc.toml` that exists.
+    pub fn load(path: Option<&str>) -> Result<Config> {
+        let path = match path {
+            Some(path) => Some(PathBuf::from(path)),
+            None => [
+                Some(PathBuf::from(CONFIG_FILE)),
+                config_dir().map(|dir| dir.join("aoc").join(CONFIG_FILE)),
+            ]
+            .into_iter()
+            .flatten()
+            .find(|path| path.exists()),
+        };
+        let mut config = Config::default();
+        if let Some(path) = path {
+            let label = path.display().to_string();
+            let source = fs::read_to_string(&path).map_err(|err| Error::io(&label, err))?;
+            config.merge(&source).map_err(|err| err.in_file(&label))?;
+        }
+        config.merge_env(|name| env::var(name).ok())?;
+        Ok(config)
+    }
+
+    /// `relative` within the inputs directory.
+    pub fn in_inputs(&self, relative: &str) -> String {
+        match self.inputs_dir.as_str() {
+            "." | "" => relative.to_string(),
+            dir => Path::new(dir).join(relative).display().to_string(),
+        }
+    }
+
+    pub fn input_path(&self, day: &Day) -> String {
+        self.in_inputs(&day.input_path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::error::Error;
+    use crate::runner::Format;
+    use crate::trace::Level;
+    use crate::y2023::DAYS;
+
+    use super::Config;
+
+    #[test]
+    fn merge_file() {
+        let mut config = Config::default();
+        config
+            .merge(
+                "# project settings
+inputs_dir = \"inputs\"
+
+year = 2022
+session_file = \"/run/aoc/session\"
+format = \"json\"
+",
+            )
+            .unwrap();
+        assert_eq!(config.inputs_dir, "inputs");
+        assert_eq!(config.fixtures_dir, "fixtures");
+        assert_eq!(config.year, 2022);
+        assert_eq!(config.session_file, Some(PathBuf::from("/run/aoc/session")));
+        assert_eq!(config.format, Format::Json);
+        assert_eq!(config.input_path(&DAYS[4]), "inputs/2023/5_input.txt");
+        assert_eq!(Config::default().input_path(&DAYS[4]), "2023/5_input.txt");
+    }
+
+    #[test]
+    fn merge_errors() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.merge("\nyears = 2022"),
+            Err(Error::parse(1, "years", "unknown setting `years`").on_line(2))
+        );
+        assert_eq!(
+            config.merge("format = \"yaml\""),
+            Err(Error::parse(
+                10,
+                "\"yaml\"",
+                "invalid format `yaml`, expected text or json"
+            )
+            .on_line(1))
+        );
+        assert_eq!(
+            config.merge("inputs_dir = inputs"),
+            Err(Error::parse(14, "inputs", "expected a quoted string or an integer").on_line(1))
+        );
+        assert_eq!(
+            config.merge("[paths]"),
+            Err(Error::parse(1, "[paths]", "expected `<key> = <value>`").on_line(1))
+        );
+    }
+
+    #[test]
+    fn merge_environment() {
+        let mut config = Config::default();
+        config
+            .merge_env(|name| match name {
+                "AOC_FIXTURES_DIR" => Some("examples".to_string()),
+                "AOC_FORMAT" => Some("json".to_string()),
+                "AOC_TRACE" => Some("verbose".to_string()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(config.fixtures_dir, "examples");
+        assert_eq!(config.format, Format::Json);
+        assert_eq!(config.trace, Level::Verbose);
+        assert_eq!(
+            config.merge_env(|name| (name == "AOC_YEAR").then(|| "next".to_string())),
+            Err(Error::semantic(
+                "AOC_YEAR: invalid year `next`, expected e.g. `2023`"
+            ))
+        );
+    }
+}
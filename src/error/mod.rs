@@ -0,0 +1,189 @@
+//! Crate-wide error type, locating parse errors precisely enough for an editor to jump to them.
+
+use std::fmt::{self, Display};
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: String,
+        source: io::Error,
+    },
+    /// Malformed input. `line` and `column` are 1-based, `0` when not known yet.
+    Parse {
+        file: Option<String>,
+        line: usize,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    /// Well-formed input that does not make sense for the puzzle.
+    Semantic(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn io(path: &str, source: io::Error) -> Error {
+        Error::Io {
+            path: path.to_string(),
+            source,
+        }
+    }
+
+    /// Parse error about `text`, found at `column` of a line.
+    pub fn parse(column: usize, text: &str, message: impl Into<String>) -> Error {
+        Error::Parse {
+            file: None,
+            line: 0,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Parse error about `part`, a slice of `line`, whose column is derived from its position.
+    pub fn parse_in(line: &str, part: &str, message: impl Into<String>) -> Error {
+        Error::parse(column(line, part), part, message)
+    }
+
+    pub fn semantic(message: impl Into<String>) -> Error {
+        Error::Semantic(message.into())
+    }
+
+    /// Sets the line of a parse error raised by a parser that only saw that line.
+    pub fn on_line(self, line: usize) -> Error {
+        match self {
+            Error::Parse {
+                file,
+                column,
+                text,
+                message,
+                ..
+            } => Error::Parse {
+                file,
+                line,
+                column,
+                text,
+                message,
+            },
+            other => other,
+        }
+    }
+
+    /// Sets the file a parse error was found in.
+    pub fn in_file(self, path: &str) -> Error {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                text,
+                message,
+                ..
+            } => Error::Parse {
+                file: Some(path.to_string()),
+                line,
+                column,
+                text,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+/// 1-based column of `part` in `line`, `part` being a slice of `line`.
+pub fn column(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+    match line.get(..offset) {
+        Some(before) => before.chars().count() + 1,
+        None => 1,
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "cannot read `{}`: {}", path, source),
+            Error::Parse {
+                file,
+                line,
+                column,
+                message,
+                ..
+            } => {
+                if let Some(file) = file {
+                    write!(f, "{}:", file)?;
+                }
+                write!(f, "{}:{}: {}", line, column, message)
+            }
+            Error::Semantic(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Error::Io { path, source }, Error::Io { path: p, source: s }) => {
+                path == p && source.kind() == s.kind()
+            }
+            (
+                Error::Parse {
+                    file,
+                    line,
+                    column,
+                    text,
+                    message,
+                },
+                Error::Parse {
+                    file: f,
+                    line: l,
+                    column: c,
+                    text: t,
+                    message: m,
+                },
+            ) => file == f && line == l && column == c && text == t && message == m,
+            (Error::Semantic(a), Error::Semantic(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{column, Error};
+
+    #[test]
+    fn columns() {
+        let line = "Game 3: 8 green, 6 purple";
+        assert_eq!(column(line, &line[0..4]), 1);
+        assert_eq!(column(line, &line[19..]), 20);
+    }
+
+    #[test]
+    fn display() {
+        let line = "Game 17: 3 blue, 2 purple";
+        let err = Error::parse_in(line, &line[19..], "expected color name, got \"purple\"");
+        assert_eq!(err.to_string(), "0:20: expected color name, got \"purple\"");
+        assert_eq!(
+            err.on_line(17).in_file("2_input.txt").to_string(),
+            "2_input.txt:17:20: expected color name, got \"purple\""
+        );
+        assert_eq!(
+            Error::semantic("no seeds")
+                .in_file("5_input.txt")
+                .to_string(),
+            "no seeds"
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! Example inputs with their expected answers, checked against the solvers.
+//!
+//! Every example lives in `fixtures/<year>/eN/<name>.txt`, next to a `<name>.answers` sidecar holding
+//! one `part<1|2>: <answer>` entry per expected answer. Blank lines and lines starting with `#`
+//! are ignored, and a part without an entry is not checked, so an example may cover only one
+//! part.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::runner;
+use crate::solution::{Day, Parts};
+
+pub const FIXTURES_DIR: &str = "fixtures";
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Fixture {
+    pub year: u32,
+    pub day: u32,
+    pub name: String,
+    pub path: PathBuf,
+    /// Expected answers, by part.
+    pub expected: Vec<(u8, String)>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct PartCheck {
+    pub part: u8,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl PartCheck {
+    pub fn passed(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+impl Fixture {
+    fn parts(&self) -> Option<Parts> {
+        let one = self.expected.iter().any(|(part, _)| *part == 1);
+        let two = self.expected.iter().any(|(part, _)| *part == 2);
+        match (one, two) {
+            (true, true) => Some(Parts::Both),
+            (true, false) => Some(Parts::One),
+            (false, true) => Some(Parts::Two),
+            (false, false) => None,
+        }
+    }
+
+    /// Solves the example with `day`, comparing every part that has an expected answer.
+    pub fn check(&self, day: &Day) -> Result<Vec<PartCheck>> {
+        let Some(parts) = self.parts() else {
+            return Ok(vec![]);
+        };
+        let path = self.path.display().to_string();
+        let source = fs::read_to_string(&self.path).map_err(|err| Error::io(&path, err))?;
+        let report = runner::solve(day, &path, &source, parts)?;
+        Ok(report
+            .parts
+            .into_iter()
+            .map(|part| PartCheck {
+                part: part.part,
+                expected: self.answer(part.part).unwrap_or_default().to_string(),
+                actual: part.answer,
+            })
+            .collect())
+    }
+
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.expected
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, answer)| answer.as_str())
+    }
+}
+
+/// Parses an `.answers` sidecar.
+pub fn parse_answers(source: &str) -> Result<Vec<(u8, String)>> {
+    let mut expected: Vec<(u8, String)> = vec![];
+    for (idx, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: &str| Error::parse(1, line, message).on_line(idx + 1);
+        let (part, answer) = line
+            .split_once(':')
+            .ok_or(error("expected `part<1|2>: <answer>`"))?;
+        let part = match part.trim() {
+            "part1" => 1,
+            "part2" => 2,
+            _ => return Err(error("expected `part1` or `part2`")),
+        };
+        let answer = answer.trim();
+        if answer.is_empty() {
+            return Err(error("missing answer"));
+        }
+        if expected.iter().any(|(p, _)| *p == part) {
+            return Err(error("duplicate entry"));
+        }
+        expected.push((part, answer.to_string()));
+    }
+    Ok(expected)
+}
+
+fn sorted_entries(dir: &Path) -> Result<Vec<PathBuf>> {
+    let label = dir.display().to_string();
+    let mut paths = fs::read_dir(dir)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<io::Result<Vec<_>>>()
+        })
+        .map_err(|err| Error::io(&label, err))?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// Subdirectories of `dir` whose name is a number once `prefix` is stripped, with that number.
+fn numbered_dirs(dir: &Path, prefix: &str) -> Result<Vec<(u32, PathBuf)>> {
+    Ok(sorted_entries(dir)?
+        .into_iter()
+        .filter(|path| path.is_dir())
+        .filter_map(|path| {
+            let number = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(prefix))
+                .and_then(|number| number.parse::<u32>().ok())?;
+            Some((number, path))
+        })
+        .collect())
+}
+
+/// Lists the examples found under `dir`, ordered by year, day, then name. An example without a
+/// sidecar has no expected answer yet.
+pub fn discover(dir: &Path) -> Result<Vec<Fixture>> {
+    let mut fixtures = vec![];
+    for (year, year_dir) in numbered_dirs(dir, "")? {
+        for (day, day_dir) in numbered_dirs(&year_dir, "e")? {
+            for path in sorted_entries(&day_dir)? {
+                if path.extension().is_none_or(|ext| ext != "txt") {
+                    continue;
+                }
+                let sidecar = path.with_extension("answers");
+                let expected = match sidecar.exists() {
+                    true => {
+                        let label = sidecar.display().to_string();
+                        let source =
+                            fs::read_to_string(&sidecar).map_err(|err| Error::io(&label, err))?;
+                        parse_answers(&source).map_err(|err| err.in_file(&label))?
+                    }
+                    false => vec![],
+                };
+                fixtures.push(Fixture {
+                    year,
+                    day,
+                    name: path.file_stem().unwrap().to_string_lossy().to_string(),
+                    path,
+                    expected,
+                });
+            }
+        }
+    }
+    fixtures.sort_by_key(|fixture| (fixture.year, fixture.day));
+    Ok(fixtures)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::error::Error;
+    use crate::tools::scratch_dir;
+    use crate::y2023::DAYS;
+
+    use super::{discover, parse_answers, PartCheck};
+
+    #[test]
+    fn parse_sidecar() {
+        assert_eq!(
+            parse_answers("# expected answers\npart2: 46\n\npart1: 35\n"),
+            Ok(vec![(2, "46".to_string()), (1, "35".to_string())])
+        );
+        assert_eq!(
+            parse_answers("part3: 1"),
+            Err(Error::parse(1, "part3: 1", "expected `part1` or `part2`").on_line(1))
+        );
+        assert_eq!(
+            parse_answers("part1 1"),
+            Err(Error::parse(1, "part1 1", "expected `part<1|2>: <answer>`").on_line(1))
+        );
+        assert_eq!(
+            parse_answers("part1: 1\npart1: 2"),
+            Err(Error::parse(1, "part1: 2", "duplicate entry").on_line(2))
+        );
+    }
+
+    #[test]
+    fn discover_and_check() {
+        let root = scratch_dir("fixtures");
+        fs::create_dir_all(root.join("2023/e1")).unwrap();
+        fs::create_dir_all(root.join("2023/notes")).unwrap();
+        fs::create_dir_all(root.join("2022/e7")).unwrap();
+        fs::write(root.join("2023/e1/b.txt"), "1abc2\n").unwrap();
+        fs::write(root.join("2023/e1/b.answers"), "part1: 12\npart2: 13\n").unwrap();
+        fs::write(root.join("2023/e1/a.txt"), "").unwrap();
+        fs::write(root.join("2023/e1/README.md"), "").unwrap();
+        fs::write(root.join("2022/e7/example.txt"), "").unwrap();
+
+        let fixtures = discover(&root).unwrap();
+        assert_eq!(
+            fixtures
+                .iter()
+                .map(|f| (f.year, f.day, f.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(2022, 7, "example"), (2023, 1, "a"), (2023, 1, "b")]
+        );
+        let fixtures = &fixtures[1..];
+        assert_eq!(fixtures[0].check(&DAYS[0]), Ok(vec![]));
+        assert_eq!(
+            fixtures[1].check(&DAYS[0]),
+            Ok(vec![
+                PartCheck {
+                    part: 1,
+                    expected: "12".to_string(),
+                    actual: "12".to_string(),
+                },
+                PartCheck {
+                    part: 2,
+                    expected: "13".to_string(),
+                    actual: "12".to_string(),
+                },
+            ])
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
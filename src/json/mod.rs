@@ -0,0 +1,99 @@
+//! Minimal JSON writer for the machine-readable output of the runner.
+
+use std::fmt::{self, Display, Formatter};
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(u64),
+    String(String),
+    Array(Vec<Value>),
+    /// Members are written in the given order.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn object<'a>(members: impl IntoIterator<Item = (&'a str, Value)>) -> Value {
+        Value::Object(
+            members
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        )
+    }
+
+    pub fn string(value: impl Into<String>) -> Value {
+        Value::String(value.into())
+    }
+}
+
+fn write_string(f: &mut Formatter, value: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Number(value) => write!(f, "{}", value),
+            Value::String(value) => write_string(f, value),
+            Value::Array(values) => {
+                write!(f, "[")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Value::Object(members) => {
+                write!(f, "{{")?;
+                for (idx, (name, value)) in members.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, name)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Value;
+
+    #[test]
+    fn write() {
+        let value = Value::object([
+            ("day", Value::Number(5)),
+            ("answer", Value::string("a \"quoted\"\\path\n\u{1}")),
+            (
+                "parts",
+                Value::Array(vec![Value::Bool(true), Value::Null, Value::Array(vec![])]),
+            ),
+            ("empty", Value::Object(vec![])),
+        ]);
+        assert_eq!(
+            value.to_string(),
+            r#"{"day":5,"answer":"a \"quoted\"\\path\n\u0001","parts":[true,null,[]],"empty":{}}"#
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Advent of Code solutions, along with the tooling running them.
+//!
+//! Every event lives in its own `yYYYY` module, with one `eN` module per day implementing
+//! [`Solution`]; [`YEARS`] lists them for the runner.
+
+pub mod y2023;
+
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod client;
+pub mod config;
+pub mod error;
+pub mod fixtures;
+pub mod json;
+pub mod memory;
+pub mod repl;
+pub mod runner;
+pub mod scaffold;
+pub mod serve;
+pub mod solution;
+pub mod submit;
+pub mod tools;
+pub mod trace;
+pub mod watch;
+
+pub use error::Error;
+pub use solution::{Day, Parts, Solution, Year};
+
+pub static YEARS: &[Year] = &[Year::new(2023, y2023::DAYS)];
+
+/// Year used when none is given: the latest registered event.
+pub fn latest_year() -> u32 {
+    YEARS.iter().map(|year| year.number).max().unwrap_or(2023)
+}
+
+/// Looks `number` up in [`YEARS`], with an error listing the available years.
+pub fn find_year(number: u32) -> Result<&'static Year, String> {
+    YEARS
+        .iter()
+        .find(|year| year.number == number)
+        .ok_or(format!(
+            "unknown year `{}` (available: {})",
+            number,
+            YEARS
+                .iter()
+                .map(|y| y.number.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+}
+
+/// Looks day `number` of `year` up, with an error listing the available days.
+pub fn find_day(year: u32, number: u32) -> Result<&'static Day, String> {
+    let days = find_year(year)?.days;
+    days.iter().find(|day| day.number == number).ok_or(format!(
+        "unknown day `e{}` of {} (available: {})",
+        number,
+        year,
+        days.iter().map(|d| d.name()).collect::<Vec<_>>().join(", ")
+    ))
+}
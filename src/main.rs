@@ -1,24 +1,208 @@
-use std::env;
+use std::io;
+use std::net::TcpListener;
+use std::path::Path;
+use std::{env, process};
 
-pub mod e1;
-pub mod e2;
-pub mod e3;
-pub mod e4;
-pub mod e5;
+use aoc2023::answers::Answers;
+use aoc2023::bench::{self, Baseline};
+use aoc2023::cli::{self, BenchOptions, Command, RunOptions};
+use aoc2023::client::{self, Client};
+use aoc2023::config::Config;
+use aoc2023::repl::{self, History};
+use aoc2023::runner::{DayOutcome, Format};
+use aoc2023::submit::{self, Verdict};
+use aoc2023::{find_day, find_year, runner, scaffold, serve, solution, trace, watch, Parts};
+
+fn run(config: &Config, options: RunOptions) -> Result<bool, String> {
+    let day = find_day(options.year, options.day)?;
+    let path = options.input.unwrap_or(config.input_path(day));
+    if options.format == Format::Json {
+        let outcome = runner::run_day(day, &path, options.parts);
+        let results = runner::json_results(day, &outcome);
+        println!("{}", runner::json_document(results));
+        return Ok(matches!(outcome, DayOutcome::Solved(_)));
+    }
+    let source = runner::read_input(&path).map_err(|err| err.to_string())?;
+    runner::run(day, &path, &source, options.parts).map_err(|err| err.to_string())?;
+    Ok(true)
+}
+
+fn run_bench(config: &Config, options: BenchOptions) -> Result<bool, String> {
+    let day = find_day(options.year, options.day)?;
+    let path = options.input.unwrap_or(config.input_path(day));
+    let source = runner::read_input(&path).map_err(|err| err.to_string())?;
+    let measures = bench::bench(day, &path, &source, options.parts, options.runs)
+        .map_err(|err| err.to_string())?;
+    let baseline = match options.baseline {
+        Some(path) => Some(Baseline::load(&path).map_err(|err| err.to_string())?),
+        None => None,
+    };
+    let (table, regressed) =
+        bench::format_report(day, &measures, baseline.as_ref(), options.threshold);
+    println!("{} ({} runs)\n{}", day.name(), options.runs, table);
+    if let Some(path) = options.save {
+        bench::save(&path, day.number, &measures).map_err(|err| err.to_string())?;
+        println!("Saved the baseline of {} to {}", day.name(), path);
+    }
+    Ok(!regressed)
+}
+
+fn explore(config: &Config, year: u32, day: u32, input: Option<String>) -> Result<(), String> {
+    let day = find_day(year, day)?;
+    let path = input.unwrap_or(config.input_path(day));
+    let source = runner::read_input(&path).map_err(|err| err.to_string())?;
+    let session = day
+        .explore(&source)
+        .map_err(|err| err.in_file(&path).to_string())?;
+    let history_path = config.in_inputs(&repl::history_path(year));
+    let mut history = History::load(&history_path)
+        .map_err(|err| format!("cannot read `{}`: {}", history_path, err))?;
+    println!(
+        "Loaded {} from {}, enter `help` to list the commands",
+        day.name(),
+        path
+    );
+    repl::repl(
+        &*session,
+        &day.name(),
+        io::stdin().lock(),
+        &mut io::stdout(),
+        &mut history,
+    )
+    .map_err(|err| err.to_string())
+}
+
+fn fetch(config: &Config, year: u32, day: u32) -> Result<(), String> {
+    let client = Client::new(client::session_token(config.session_file.as_deref())?);
+    let path = config.in_inputs(&solution::input_path(year, day));
+    match client::fetch_input(&client, year, day, Path::new(&path))? {
+        true => println!("Downloaded input of {} e{} to {}", year, day, path),
+        false => println!("Input of {} e{} already in {}", year, day, path),
+    }
+    Ok(())
+}
+
+fn submit(
+    config: &Config,
+    year: u32,
+    day: u32,
+    part: u8,
+    answer: Option<String>,
+    input: Option<String>,
+) -> Result<bool, String> {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let found = find_day(year, day)?;
+            let path = input.unwrap_or(config.input_path(found));
+            let source = runner::read_input(&path).map_err(|err| err.to_string())?;
+            let parts = if part == 1 { Parts::One } else { Parts::Two };
+            let report =
+                runner::solve(found, &path, &source, parts).map_err(|err| err.to_string())?;
+            report.parts[0].answer.clone()
+        }
+    };
+    let client = Client::new(client::session_token(config.session_file.as_deref())?);
+    let (verdict, wait) = submit::submit(
+        &client,
+        &config.in_inputs(&submit::history_path(year)),
+        year,
+        day,
+        part,
+        &answer,
+    )?;
+    match wait {
+        0 => println!("e{} part {}: `{}` -> {}", day, part, answer, verdict),
+        _ => println!(
+            "e{} part {}: `{}` -> {} (wait {}s before the next attempt)",
+            day, part, answer, verdict, wait
+        ),
+    }
+    Ok(verdict == Verdict::Correct)
+}
+
+/// Runs `command`, returning `Ok(false)` when it completed but found a failure to report.
+fn execute(config: &Config, command: Command) -> Result<bool, String> {
+    match command {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::All {
+            year,
+            parts,
+            format,
+            jobs,
+        } => runner::run_all(find_year(year)?.days, config, parts, format, jobs),
+        Command::Check { year, answers } => {
+            let days = find_year(year)?.days;
+            let answers = Answers::load(&answers).map_err(|err| err.to_string())?;
+            return Ok(runner::check(days, config, &answers));
+        }
+        Command::Bench(options) => return run_bench(config, options),
+        Command::Watch {
+            year,
+            day,
+            parts,
+            input,
+        } => {
+            let day = find_day(year, day)?;
+            let path = input.unwrap_or(config.input_path(day));
+            watch::watch(day, &path, Path::new(&config.fixtures_dir), parts)
+        }
+        Command::Repl { year, day, input } => explore(config, year, day, input)?,
+        Command::Serve { address, max_body } => {
+            let listener = TcpListener::bind(&address)
+                .map_err(|err| format!("cannot listen on {}: {}", address, err))?;
+            println!("Serving on http://{} (Ctrl-C to stop)", address);
+            serve::serve(listener, max_body).map_err(|err| err.to_string())?;
+        }
+        Command::Fetch { year, day } => fetch(config, year, day)?,
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+            input,
+        } => return submit(config, year, day, part, answer, input),
+        Command::New { year, day } => {
+            for path in scaffold::scaffold(Path::new("."), &config.fixtures_dir, year, day)? {
+                println!("Created {}", path.display());
+            }
+            println!("Registered e{} in {}", day, scaffold::registry_path(year));
+        }
+        Command::Run(options) => return run(config, options),
+    }
+    Ok(true)
+}
+
+/// Executes `command` with the tracing asked for in `config`, then writes the Chrome trace.
+fn traced(config: &Config, command: Command) -> Result<bool, String> {
+    trace::init(config.trace, config.trace_file.is_some());
+    let ok = execute(config, command)?;
+    if let Some(path) = &config.trace_file {
+        trace::write_chrome_trace(path).map_err(|err| err.to_string())?;
+    }
+    Ok(ok)
+}
 
-pub mod tools;
 fn main() {
-    let args = env::args().collect::<Vec<String>>();
-    match args.len() {
-        1 => panic!("Pass exercise number as argument: e1, e2, ..."),
-        2 => match args[1].as_str() {
-            "e1" => e1::run(),
-            "e2" => e2::run(),
-            "e3" => e3::run(),
-            "e4" => e4::run(),
-            "e5" => e5::run(),
-            _ => panic!("Unknown exercise"),
-        },
-        _ => panic!("Too much arguments"),
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let mut config = match Config::load(cli::config_path(&args)) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(2);
+        }
+    };
+    let code = match cli::parse_args(&args, &mut config).map(|command| traced(&config, command)) {
+        Ok(Ok(true)) => 0,
+        Ok(Ok(false)) => 1,
+        Ok(Err(message)) => {
+            eprintln!("error: {}", message);
+            1
+        }
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, cli::USAGE);
+            2
+        }
     };
+    process::exit(code);
 }
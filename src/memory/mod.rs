@@ -0,0 +1,157 @@
+//! Heap usage of the solutions, counted by a global allocator when built with the `memory`
+//! feature (`cargo run --features memory -- all`).
+//!
+//! Counters are kept per thread, so that days run concurrently are measured apart. Memory freed
+//! on another thread than the one allocating it is not accounted for.
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct Usage {
+    /// Highest amount of memory in use at once, above what was in use before.
+    pub peak: u64,
+    pub allocations: u64,
+    /// Bytes allocated in total, including those freed since.
+    pub allocated: u64,
+}
+
+impl Usage {
+    /// Usage of a sequence of phases: the highest of the peaks and the sum of the rest.
+    pub fn combine(self, other: Usage) -> Usage {
+        Usage {
+            peak: self.peak.max(other.peak),
+            allocations: self.allocations + other.allocations,
+            allocated: self.allocated + other.allocated,
+        }
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{}B", bytes),
+        1024..=1048575 => format!("{:.1}KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1}MiB", bytes as f64 / 1048576.0),
+    }
+}
+
+#[cfg(feature = "memory")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    thread_local! {
+        /// Bytes in use, which may go below zero when freeing memory from another thread.
+        pub static CURRENT: Cell<i64> = const { Cell::new(0) };
+        pub static PEAK: Cell<i64> = const { Cell::new(0) };
+        pub static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+        pub static ALLOCATED: Cell<u64> = const { Cell::new(0) };
+    }
+
+    fn record(allocated: i64) {
+        // Thread-locals are unavailable while a thread is torn down: skip what it frees then.
+        let _ = CURRENT.try_with(|current| {
+            current.set(current.get() + allocated);
+            PEAK.with(|peak| peak.set(peak.get().max(current.get())));
+            if allocated > 0 {
+                ALLOCATIONS.with(|count| count.set(count.get() + 1));
+                ALLOCATED.with(|total| total.set(total.get() + allocated as u64));
+            }
+        });
+    }
+
+    pub struct Counter;
+
+    unsafe impl GlobalAlloc for Counter {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record(layout.size() as i64);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record(-(layout.size() as i64));
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new = System.realloc(ptr, layout, new_size);
+            if !new.is_null() {
+                record(-(layout.size() as i64));
+                record(new_size as i64);
+            }
+            new
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counter = Counter;
+}
+
+/// Whether heap usage is counted, which depends on the `memory` feature.
+pub const fn enabled() -> bool {
+    cfg!(feature = "memory")
+}
+
+/// Runs `f`, returning its result along with the heap usage of the current thread meanwhile, if
+/// counted.
+#[cfg(feature = "memory")]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    use counting::{ALLOCATED, ALLOCATIONS, CURRENT, PEAK};
+
+    let start = CURRENT.with(|c| c.get());
+    let outer_peak = PEAK.with(|p| p.replace(start));
+    let (allocations, allocated) = (ALLOCATIONS.with(|a| a.get()), ALLOCATED.with(|a| a.get()));
+    let result = f();
+    let peak = PEAK.with(|p| {
+        let peak = p.get();
+        // Keep the peak of an enclosing measure, which started from a lower level.
+        p.set(peak.max(outer_peak));
+        peak
+    });
+    let usage = Usage {
+        peak: (peak - start).max(0) as u64,
+        allocations: ALLOCATIONS.with(|a| a.get()) - allocations,
+        allocated: ALLOCATED.with(|a| a.get()) - allocated,
+    };
+    (result, Some(usage))
+}
+
+#[cfg(not(feature = "memory"))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    (f(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{enabled, format_bytes, measure, Usage};
+
+    #[test]
+    fn count_allocations() {
+        assert_eq!(format_bytes(999), "999B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 * 1048576), "3.0MiB");
+        let usage = |peak, allocations, allocated| Usage {
+            peak,
+            allocations,
+            allocated,
+        };
+        assert_eq!(usage(5, 1, 8).combine(usage(3, 2, 20)), usage(5, 3, 28));
+
+        let ((inner, kept), outer) = measure(|| {
+            let kept = vec![0u8; 4096];
+            let (_, inner) = measure(|| vec![0u8; 1 << 16].len());
+            (inner, kept)
+        });
+        drop(kept);
+        if !enabled() {
+            assert_eq!((inner, outer), (None, None));
+            return;
+        }
+        let (inner, outer) = (inner.unwrap(), outer.unwrap());
+        assert_eq!(inner.peak, 1 << 16);
+        assert_eq!(inner.allocations, 1);
+        assert_eq!(outer.peak, 4096 + (1 << 16));
+        assert!(outer.allocations >= 2);
+        assert!(outer.allocated >= 4096 + (1 << 16));
+    }
+}
@@ -0,0 +1,277 @@
+//! Interactive exploration of the parsed input of a day, with commands registered by the day
+//! itself through [`Solution::commands`].
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+
+/// Command of a day, run on its parsed input with the words following its name.
+pub struct Command<I> {
+    pub name: &'static str,
+    /// Arguments and effect, as shown by `help`.
+    pub help: &'static str,
+    pub run: fn(&I, &[&str]) -> Result<String>,
+}
+
+/// Parses the argument at `idx`, described as `name` in errors.
+pub fn arg<T: FromStr>(args: &[&str], idx: usize, name: &str) -> Result<T> {
+    let arg = args
+        .get(idx)
+        .ok_or(Error::semantic(format!("missing {}", name)))?;
+    arg.parse()
+        .map_err(|_| Error::semantic(format!("invalid {} `{}`", name, arg)))
+}
+
+/// Parsed input of a day along with its commands, with the `Solution` erased.
+pub trait Session {
+    /// Names and help of the commands, the parts included.
+    fn commands(&self) -> Vec<(&'static str, &'static str)>;
+    /// Runs the command `name`, or returns `None` if there is no such command.
+    fn run(&self, name: &str, args: &[&str]) -> Option<Result<String>>;
+}
+
+struct Parsed<S: Solution> {
+    input: S::Input,
+    commands: Vec<Command<S::Input>>,
+}
+
+impl<S: Solution> Session for Parsed<S> {
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        let mut commands = vec![
+            ("part1", "solve part 1 on the loaded input"),
+            ("part2", "solve part 2 on the loaded input"),
+        ];
+        commands.extend(self.commands.iter().map(|c| (c.name, c.help)));
+        commands
+    }
+
+    fn run(&self, name: &str, args: &[&str]) -> Option<Result<String>> {
+        match name {
+            "part1" => Some(S::part1(&self.input).map(|answer| answer.to_string())),
+            "part2" => Some(S::part2(&self.input).map(|answer| answer.to_string())),
+            _ => self
+                .commands
+                .iter()
+                .find(|command| command.name == name)
+                .map(|command| (command.run)(&self.input, args)),
+        }
+    }
+}
+
+/// Parses `source` once for a session of `S`.
+pub fn open<S: Solution + 'static>(source: &str) -> Result<Box<dyn Session>> {
+    Ok(Box::new(Parsed::<S> {
+        input: S::parse(source)?,
+        commands: S::commands(),
+    }))
+}
+
+/// Default location of the REPL history of `year`, shared by its days.
+pub fn history_path(year: u32) -> String {
+    format!("{}/repl_history.txt", year)
+}
+
+/// Lines entered in earlier sessions, appended to `path` as they are entered when it is given.
+pub struct History {
+    entries: Vec<String>,
+    path: Option<PathBuf>,
+}
+
+impl History {
+    pub fn new() -> History {
+        History {
+            entries: vec![],
+            path: None,
+        }
+    }
+
+    /// History kept in the file at `path`, which may not exist yet.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<History> {
+        let path = path.into();
+        let entries = match fs::read_to_string(&path) {
+            Ok(source) => source.lines().map(|line| line.to_string()).collect(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => vec![],
+            Err(err) => return Err(err),
+        };
+        Ok(History {
+            entries,
+            path: Some(path),
+        })
+    }
+
+    fn push(&mut self, line: &str) -> io::Result<()> {
+        self.entries.push(line.to_string());
+        if let Some(path) = &self.path {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// Entry recalled by `!!` (the last one) or `!N` (the N-th one).
+    fn recall(&self, reference: &str) -> Option<&str> {
+        let idx = match reference {
+            "!" => self.entries.len().checked_sub(1)?,
+            n => n.parse::<usize>().ok()?.checked_sub(1)?,
+        };
+        self.entries.get(idx).map(|entry| entry.as_str())
+    }
+}
+
+impl Default for History {
+    fn default() -> History {
+        History::new()
+    }
+}
+
+/// Reads commands from `input` until it ends or `quit` is entered, printing their results on
+/// `output`. Besides the commands of `session`, `help` lists them and `history` lists the
+/// entered lines, which `!!` and `!N` run again.
+pub fn repl(
+    session: &dyn Session,
+    prompt: &str,
+    mut input: impl BufRead,
+    output: &mut impl Write,
+    history: &mut History,
+) -> io::Result<()> {
+    loop {
+        write!(output, "{}> ", prompt)?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(());
+        }
+        let mut line = line.trim().to_string();
+        if let Some(reference) = line.strip_prefix('!') {
+            match history.recall(reference) {
+                Some(entry) => {
+                    line = entry.to_string();
+                    writeln!(output, "{}", line)?;
+                }
+                None => {
+                    writeln!(output, "error: no history entry `{}`", line)?;
+                    continue;
+                }
+            }
+        }
+        let words = line.split_whitespace().collect::<Vec<&str>>();
+        let Some((&name, args)) = words.split_first() else {
+            continue;
+        };
+        if name != "history" {
+            history.push(&line)?;
+        }
+        match name {
+            "quit" | "exit" => return Ok(()),
+            "help" => {
+                let mut commands = session.commands();
+                commands.extend([
+                    ("help", "list the commands"),
+                    ("history", "list the entered lines, run again with !! or !N"),
+                    ("quit", "leave"),
+                ]);
+                let width = commands
+                    .iter()
+                    .map(|(name, _)| name.len())
+                    .max()
+                    .unwrap_or(0);
+                for (name, help) in commands {
+                    writeln!(output, "  {:<w$}  {}", name, help, w = width)?;
+                }
+            }
+            "history" => {
+                for (idx, entry) in history.entries.iter().enumerate() {
+                    writeln!(output, "{:>4}  {}", idx + 1, entry)?;
+                }
+            }
+            _ => match session.run(name, args) {
+                Some(Ok(result)) => writeln!(output, "{}", result)?,
+                Some(Err(err)) => writeln!(output, "error: {}", err)?,
+                None => writeln!(output, "error: unknown command `{}`, see `help`", name)?,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::error::Error;
+    use crate::tools::scratch_dir;
+    use crate::y2023::DAYS;
+
+    use super::{arg, repl, History};
+
+    #[test]
+    fn explore_day() {
+        let dir = scratch_dir("repl");
+        let session = DAYS[3]
+            .explore(include_str!("../../fixtures/2023/e4/example.txt"))
+            .unwrap();
+        let mut history = History::load(dir.join("history.txt")).unwrap();
+        let mut output = vec![];
+        let script = "part1\n\ncard 1\nfly\n!!\n!1\nhistory\nquit\npart2\n";
+        repl(
+            &*session,
+            "e4",
+            script.as_bytes(),
+            &mut output,
+            &mut history,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "e4> 13
+e4> e4> card 1: 4 winning numbers, worth 8 points, wins a copy of cards 2, 3, 4, 5
+e4> error: unknown command `fly`, see `help`
+e4> fly
+error: unknown command `fly`, see `help`
+e4> part1
+13
+e4>    1  part1
+   2  card 1
+   3  fly
+   4  fly
+   5  part1
+e4> "
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("history.txt")).unwrap(),
+            "part1\ncard 1\nfly\nfly\npart1\nquit\n"
+        );
+
+        let mut history = History::load(dir.join("history.txt")).unwrap();
+        let mut output = vec![];
+        repl(
+            &*session,
+            "e4",
+            "!2\n".as_bytes(),
+            &mut output,
+            &mut history,
+        )
+        .unwrap();
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .starts_with("e4> card 1\ncard 1:"));
+        fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(arg::<u32>(&["7"], 0, "card"), Ok(7));
+        assert_eq!(
+            arg::<u32>(&["x"], 0, "card"),
+            Err(Error::semantic("invalid card `x`"))
+        );
+        assert_eq!(
+            arg::<u32>(&[], 0, "card"),
+            Err(Error::semantic("missing card"))
+        );
+    }
+}
@@ -0,0 +1,589 @@
+use std::fs::read_to_string;
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use crate::answers::Answers;
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::json::Value;
+use crate::memory::{format_bytes, Usage};
+use crate::solution::{Day, PartReport, Parts, Report};
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Reads the puzzle input from `path`, or from stdin when `path` is `-`.
+pub fn read_input(path: &str) -> Result<String> {
+    if path == "-" {
+        let mut source = String::new();
+        io::stdin()
+            .read_to_string(&mut source)
+            .map_err(|err| Error::io("<stdin>", err))?;
+        return Ok(source);
+    }
+    read_to_string(path).map_err(|err| Error::io(path, err))
+}
+
+/// Solves `day` on `source`, read from `path`, so that parse errors point into that file.
+pub fn solve(day: &Day, path: &str, source: &str, parts: Parts) -> Result<Report> {
+    let file = match path {
+        "-" => "<stdin>",
+        path => path,
+    };
+    day.solve(source, parts).map_err(|err| err.in_file(file))
+}
+
+pub fn run(day: &Day, path: &str, source: &str, parts: Parts) -> Result<()> {
+    let report = solve(day, path, source, parts)?;
+    println!(
+        "{} (parsed in {})",
+        day.name(),
+        format_duration(report.parse_time)
+    );
+    for part in report.parts {
+        println!(
+            "Part {}: {} ({})",
+            part.part,
+            part.answer,
+            format_duration(part.time)
+        );
+    }
+    Ok(())
+}
+
+/// Lays out `rows` in columns, the first row being the header. Columns flagged in `right` are
+/// right-aligned, which suits durations.
+pub fn format_table(rows: &[Vec<String>], right: &[bool]) -> String {
+    let mut widths = vec![0; right.len()];
+    for row in rows {
+        for (idx, cell) in row.iter().enumerate() {
+            widths[idx] = usize::max(widths[idx], cell.chars().count());
+        }
+    }
+    let format_row = |row: &Vec<String>| {
+        row.iter()
+            .enumerate()
+            .map(|(idx, cell)| match right[idx] {
+                true => format!("{:>w$}", cell, w = widths[idx]),
+                false => format!("{:<w$}", cell, w = widths[idx]),
+            })
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![];
+    for (idx, row) in rows.iter().enumerate() {
+        lines.push(format_row(row));
+        if idx == 0 || idx == rows.len() - 2 {
+            lines.push("-".repeat(widths.iter().sum::<usize>() + 2 * (widths.len() - 1)));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Output format of the runner.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+pub enum DayOutcome {
+    /// The input could not be read.
+    Missing(Error),
+    Failed(Error),
+    Solved(Report),
+}
+
+/// Reads the input of `day` from `path` and solves it.
+pub fn run_day(day: &Day, path: &str, parts: Parts) -> DayOutcome {
+    let source = match read_input(path) {
+        Ok(source) => source,
+        Err(err) => return DayOutcome::Missing(err),
+    };
+    match solve(day, path, &source, parts) {
+        Ok(report) => DayOutcome::Solved(report),
+        Err(err) => DayOutcome::Failed(err),
+    }
+}
+
+fn json_usage(usage: Usage) -> Value {
+    Value::object([
+        ("peak_bytes", Value::Number(usage.peak)),
+        ("allocations", Value::Number(usage.allocations)),
+        ("allocated_bytes", Value::Number(usage.allocated)),
+    ])
+}
+
+/// Describes `outcome` with one JSON object per solved part, or a single one when `day` could
+/// not be solved. Every part carries the parse time and the heap usage of the whole day.
+pub fn json_results(day: &Day, outcome: &DayOutcome) -> Vec<Value> {
+    let result = |part, answer, timings, memory, status, error| {
+        Value::object([
+            ("year", Value::Number(day.year as u64)),
+            ("day", Value::Number(day.number as u64)),
+            ("part", part),
+            ("answer", answer),
+            ("timings", timings),
+            ("memory", memory),
+            ("status", Value::string(status)),
+            ("error", error),
+        ])
+    };
+    let nanos = |duration: Duration| Value::Number(duration.as_nanos() as u64);
+    match outcome {
+        DayOutcome::Missing(err) => vec![result(
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            "missing",
+            Value::string(err.to_string()),
+        )],
+        DayOutcome::Failed(err) => vec![result(
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            "error",
+            Value::string(err.to_string()),
+        )],
+        DayOutcome::Solved(report) => report
+            .parts
+            .iter()
+            .map(|part| {
+                result(
+                    Value::Number(part.part as u64),
+                    Value::string(part.answer.clone()),
+                    Value::object([
+                        ("parse_ns", nanos(report.parse_time)),
+                        ("solve_ns", nanos(part.time)),
+                    ]),
+                    match (part.memory, report.memory) {
+                        (Some(part), Some(day)) => {
+                            Value::object([("part", json_usage(part)), ("day", json_usage(day))])
+                        }
+                        _ => Value::Null,
+                    },
+                    "ok",
+                    Value::Null,
+                )
+            })
+            .collect(),
+    }
+}
+
+pub fn json_document(results: Vec<Value>) -> Value {
+    Value::object([("results", Value::Array(results))])
+}
+
+/// Cells of the heap usage columns.
+fn usage_cells(usage: Usage) -> [String; 3] {
+    [
+        format_bytes(usage.peak),
+        usage.allocations.to_string(),
+        format_bytes(usage.allocated),
+    ]
+}
+
+/// Table of the answers and timings of `outcomes`, followed by their errors. Heap usage is shown
+/// too when it was counted, that of parsing on the first part of the day as for times.
+fn format_outcomes(config: &Config, outcomes: &[(&Day, DayOutcome)]) -> String {
+    let memory = outcomes.iter().any(
+        |(_, outcome)| matches!(outcome, DayOutcome::Solved(report) if report.memory.is_some()),
+    );
+    let mut header = vec!["Day", "Part", "Answer", "Parse", "Solve", "Total"];
+    if memory {
+        header.extend(["Peak", "Allocs", "Allocated"]);
+    }
+    let mut rows = vec![header
+        .iter()
+        .map(|h| h.to_string())
+        .collect::<Vec<String>>()];
+    let (mut parse_total, mut solve_total) = (Duration::ZERO, Duration::ZERO);
+    let mut usage_total = Usage::default();
+    let mut errors = vec![];
+
+    let failure_row = |day: &Day, message: String| {
+        let mut row = vec![day.name(), "-".to_string(), message];
+        row.resize(header.len(), String::new());
+        row
+    };
+
+    for (day, outcome) in outcomes {
+        let report = match outcome {
+            DayOutcome::Solved(report) => report,
+            DayOutcome::Missing(_) => {
+                rows.push(failure_row(
+                    day,
+                    format!("missing {}", config.input_path(day)),
+                ));
+                continue;
+            }
+            DayOutcome::Failed(err) => {
+                rows.push(failure_row(day, "error".to_string()));
+                errors.push(format!("{}: {}", day.name(), err));
+                continue;
+            }
+        };
+        parse_total += report.parse_time;
+        let day_usage = report.memory.unwrap_or_default();
+        let later_usage = report
+            .parts
+            .iter()
+            .skip(1)
+            .fold(Usage::default(), |usage, part| {
+                usage.combine(part.memory.unwrap_or_default())
+            });
+        for (idx, part) in report.parts.iter().enumerate() {
+            // The parse time is only accounted once, on the first part of the day.
+            let parse_time = match idx {
+                0 => report.parse_time,
+                _ => Duration::ZERO,
+            };
+            solve_total += part.time;
+            let mut row = vec![
+                day.name(),
+                part.part.to_string(),
+                part.answer.clone(),
+                match idx {
+                    0 => format_duration(parse_time),
+                    _ => String::new(),
+                },
+                format_duration(part.time),
+                format_duration(parse_time + part.time),
+            ];
+            if memory {
+                // What the day used besides its later parts: parsing and this part.
+                let usage = match idx {
+                    0 => Usage {
+                        peak: day_usage.peak,
+                        allocations: day_usage
+                            .allocations
+                            .saturating_sub(later_usage.allocations),
+                        allocated: day_usage.allocated.saturating_sub(later_usage.allocated),
+                    },
+                    _ => part.memory.unwrap_or_default(),
+                };
+                usage_total = usage_total.combine(usage);
+                row.extend(usage_cells(usage));
+            }
+            rows.push(row);
+        }
+    }
+
+    let mut total = vec![
+        "Total".to_string(),
+        String::new(),
+        String::new(),
+        format_duration(parse_total),
+        format_duration(solve_total),
+        format_duration(parse_total + solve_total),
+    ];
+    let mut right = vec![false, false, false, true, true, true];
+    if memory {
+        total.extend(usage_cells(usage_total));
+        right.extend([true, true, true]);
+    }
+    rows.push(total);
+    let mut lines = vec![format_table(&rows, &right)];
+    lines.extend(errors);
+    lines.join("\n")
+}
+
+/// Runs every day on its input in the inputs directory of `config`, spread over `jobs` threads.
+/// Outcomes are returned in the order of `days` whatever order they complete in.
+pub fn run_days<'a>(
+    days: &'a [Day],
+    config: &Config,
+    parts: Parts,
+    jobs: usize,
+) -> Vec<(&'a Day, DayOutcome)> {
+    let next = AtomicUsize::new(0);
+    let outcomes = Mutex::new((0..days.len()).map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(day) = days.get(idx) else { break };
+                let outcome = run_day(day, &config.input_path(day), parts);
+                outcomes.lock().unwrap()[idx] = Some(outcome);
+            });
+        }
+    });
+    days.iter()
+        .zip(outcomes.into_inner().unwrap())
+        .map(|(day, outcome)| (day, outcome.expect("every day is run")))
+        .collect()
+}
+
+pub fn run_all(days: &[Day], config: &Config, parts: Parts, format: Format, jobs: usize) {
+    let outcomes = run_days(days, config, parts, jobs);
+    match format {
+        Format::Text => println!("{}", format_outcomes(config, &outcomes)),
+        Format::Json => {
+            let results = outcomes
+                .iter()
+                .flat_map(|(day, outcome)| json_results(day, outcome))
+                .collect();
+            println!("{}", json_document(results));
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Match,
+    Mismatch { expected: String },
+    Unrecorded,
+}
+
+pub fn compare(answers: &Answers, day: u32, part: &PartReport) -> Outcome {
+    match answers.get(day, part.part) {
+        Some(expected) if expected == part.answer => Outcome::Match,
+        Some(expected) => Outcome::Mismatch {
+            expected: expected.to_string(),
+        },
+        None => Outcome::Unrecorded,
+    }
+}
+
+/// Runs every day against the recorded answers, returning whether all of them still match.
+pub fn check(days: &[Day], config: &Config, answers: &Answers) -> bool {
+    let (mut matches, mut failures, mut unrecorded) = (0, 0, 0);
+    for day in days {
+        let path = config.input_path(day);
+        let source = match read_input(&path) {
+            Ok(source) => source,
+            Err(_) if !answers.has_day(day.number) => {
+                println!("{}: skipped, no input and no recorded answer", day.name());
+                continue;
+            }
+            Err(err) => {
+                println!("{}: {}", day.name(), err);
+                failures += 1;
+                continue;
+            }
+        };
+        let report = match solve(day, &path, &source, Parts::Both) {
+            Ok(report) => report,
+            Err(err) => {
+                println!("{}: {}", day.name(), err);
+                failures += 1;
+                continue;
+            }
+        };
+        for part in report.parts {
+            match compare(answers, day.number, &part) {
+                Outcome::Match => {
+                    println!("{} part {}: ok", day.name(), part.part);
+                    matches += 1;
+                }
+                Outcome::Mismatch { expected } => {
+                    println!("{} part {}: MISMATCH", day.name(), part.part);
+                    println!("    expected: {}", expected);
+                    println!("    actual:   {}", part.answer);
+                    failures += 1;
+                }
+                Outcome::Unrecorded => {
+                    println!(
+                        "{} part {}: no recorded answer (got {})",
+                        day.name(),
+                        part.part,
+                        part.answer
+                    );
+                    unrecorded += 1;
+                }
+            }
+        }
+    }
+    println!(
+        "\n{} ok, {} failed, {} not recorded",
+        matches, failures, unrecorded
+    );
+    failures == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use std::fs;
+
+    use crate::answers::Answers;
+    use crate::config::Config;
+    use crate::memory::Usage;
+    use crate::solution::{PartReport, Parts};
+    use crate::tools::scratch_dir;
+
+    use crate::error::Error;
+    use crate::solution::Report;
+    use crate::y2023::DAYS;
+
+    use super::{
+        compare, format_outcomes, format_table, json_document, json_results, run_days, DayOutcome,
+        Outcome,
+    };
+
+    #[test]
+    fn compare_answers() {
+        let answers = Answers::parse("e3 1 4361\ne3 2 467835").unwrap();
+        let report = |part: u8, answer: &str| PartReport {
+            part,
+            answer: answer.to_string(),
+            time: Duration::ZERO,
+            memory: None,
+        };
+        assert_eq!(compare(&answers, 3, &report(1, "4361")), Outcome::Match);
+        assert_eq!(
+            compare(&answers, 3, &report(2, "467836")),
+            Outcome::Mismatch {
+                expected: "467835".to_string()
+            }
+        );
+        assert_eq!(compare(&answers, 4, &report(1, "13")), Outcome::Unrecorded);
+    }
+
+    #[test]
+    fn json_output() {
+        let report = Report {
+            parse_time: Duration::from_nanos(1500),
+            memory: None,
+            parts: vec![PartReport {
+                part: 2,
+                answer: "281".to_string(),
+                time: Duration::from_nanos(20),
+                memory: None,
+            }],
+        };
+        let failure = DayOutcome::Failed(Error::semantic("no \"seeds\""));
+        let mut results = json_results(&DAYS[0], &DayOutcome::Solved(report));
+        results.extend(json_results(&DAYS[4], &failure));
+        assert_eq!(
+            json_document(results).to_string(),
+            r#"{"results":[{"year":2023,"day":1,"part":2,"answer":"281","timings":{"parse_ns":1500,"solve_ns":20},"memory":null,"status":"ok","error":null},{"year":2023,"day":5,"part":null,"answer":null,"timings":null,"memory":null,"status":"error","error":"no \"seeds\""}]}"#
+        );
+    }
+
+    #[test]
+    fn parallel_days() {
+        let root = scratch_dir("run-days");
+        let config = Config {
+            inputs_dir: root.display().to_string(),
+            ..Config::default()
+        };
+        fs::create_dir_all(root.join("2023")).unwrap();
+        for day in [2, 3, 5] {
+            let example =
+                fs::read_to_string(format!("fixtures/2023/e{}/example.txt", day)).unwrap();
+            fs::write(root.join(format!("2023/{}_input.txt", day)), example).unwrap();
+        }
+        fs::write(root.join("2023/4_input.txt"), "Card 1: 41 48 |\nCard 2").unwrap();
+
+        let summary = |jobs| {
+            run_days(&DAYS[..5], &config, Parts::One, jobs)
+                .iter()
+                .map(|(day, outcome)| match outcome {
+                    DayOutcome::Missing(_) => format!("{} missing", day.name()),
+                    DayOutcome::Failed(_) => format!("{} failed", day.name()),
+                    DayOutcome::Solved(report) => {
+                        format!("{} {}", day.name(), report.parts[0].answer)
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        let expected = vec!["e1 missing", "e2 8", "e3 4361", "e4 failed", "e5 35"];
+        assert_eq!(summary(1), expected);
+        assert_eq!(summary(3), expected);
+        assert_eq!(summary(64), expected);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn memory_columns() {
+        let usage = |peak, allocations, allocated| Usage {
+            peak,
+            allocations,
+            allocated,
+        };
+        let report = Report {
+            parse_time: Duration::from_micros(500),
+            memory: Some(usage(4096, 12, 10000)),
+            parts: vec![
+                PartReport {
+                    part: 1,
+                    answer: "35".to_string(),
+                    time: Duration::from_micros(20),
+                    memory: Some(usage(2048, 3, 2100)),
+                },
+                PartReport {
+                    part: 2,
+                    answer: "46".to_string(),
+                    time: Duration::from_micros(30),
+                    memory: Some(usage(1024, 4, 3000)),
+                },
+            ],
+        };
+        let small = Report {
+            parse_time: Duration::from_micros(100),
+            memory: Some(usage(512, 2, 300)),
+            parts: vec![PartReport {
+                part: 1,
+                answer: "142".to_string(),
+                time: Duration::from_micros(10),
+                memory: Some(usage(100, 1, 50)),
+            }],
+        };
+        let outcomes = [
+            (&DAYS[4], DayOutcome::Solved(report.clone())),
+            (&DAYS[3], DayOutcome::Failed(Error::semantic("no card"))),
+            (&DAYS[0], DayOutcome::Solved(small)),
+        ];
+        // The first row of a day carries its parsing, so that the rows add up to the total:
+        // 8 + 4 + 2 allocations of 7000 + 3000 + 300 bytes.
+        assert_eq!(
+            format_outcomes(&Config::default(), &outcomes),
+            "Day    Part  Answer    Parse    Solve    Total    Peak  Allocs  Allocated
+-------------------------------------------------------------------------
+e5     1     35      0.500ms  0.020ms  0.520ms  4.0KiB       8     6.8KiB
+e5     2     46               0.030ms  0.030ms  1.0KiB       4     2.9KiB
+e4     -     error
+e1     1     142     0.100ms  0.010ms  0.110ms    512B       2       300B
+-------------------------------------------------------------------------
+Total                0.600ms  0.060ms  0.660ms  4.0KiB      14    10.1KiB
+e4: no card"
+        );
+        let json = json_results(&DAYS[4], &outcomes[0].1)[0].to_string();
+        assert!(
+            json.contains(r#""memory":{"part":{"peak_bytes":2048,"allocations":3,"allocated_bytes":2100},"day":{"peak_bytes":4096,"allocations":12,"allocated_bytes":10000}}"#),
+            "{}",
+            json
+        );
+    }
+
+    #[test]
+    fn table_layout() {
+        let rows = vec![
+            vec!["Day".to_string(), "Answer".to_string(), "Time".to_string()],
+            vec!["e1".to_string(), "54388".to_string(), "1.5ms".to_string()],
+            vec!["e10".to_string(), "7".to_string(), "12.25ms".to_string()],
+            vec!["Total".to_string(), String::new(), "13.75ms".to_string()],
+        ];
+        assert_eq!(
+            format_table(&rows, &[false, false, true]),
+            "Day    Answer     Time
+----------------------
+e1     54388     1.5ms
+e10    7       12.25ms
+----------------------
+Total          13.75ms"
+        );
+    }
+}
@@ -0,0 +1,367 @@
+//! Generation of the module of a new day, following the shape shared by every `eN` module.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("day.rs.tmpl");
+
+/// Module of a year without any day yet.
+const YEAR_TEMPLATE: &str = "//! Advent of Code {year}.
+
+use crate::solution::Day;
+
+pub static DAYS: &[Day] = &[];
+";
+
+/// File declaring the year modules and the `YEARS` registry.
+pub const YEARS_FILE: &str = "src/lib.rs";
+
+/// Sidecar created next to the example, listing its expected answers once they are known.
+const ANSWERS_TEMPLATE: &str = "# part1: <answer>\n# part2: <answer>\n";
+
+/// File declaring the day modules of `year` and its `DAYS` registry.
+pub fn registry_path(year: u32) -> String {
+    format!("src/y{}/mod.rs", year)
+}
+
+pub fn fixture_path(fixtures_dir: &str, year: u32, day: u32) -> String {
+    format!("{}/{}/e{}/example.txt", fixtures_dir, year, day)
+}
+
+pub fn answers_path(fixtures_dir: &str, year: u32, day: u32) -> String {
+    format!("{}/{}/e{}/example.answers", fixtures_dir, year, day)
+}
+
+/// Splits the comma-separated `entries` of a list, ignoring the commas between parentheses.
+fn split_entries(entries: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0;
+    entries
+        .split(move |c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => (),
+            }
+            c == ',' && depth == 0
+        })
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+}
+
+/// Inserts the `module` declaration after the last one starting with `prefix`, or before the
+/// imports if there is none yet, and appends `entry` to the `registry` list.
+fn declare(
+    source: &str,
+    prefix: &str,
+    module: String,
+    registry: &str,
+    entry: String,
+) -> Result<String, String> {
+    let mut lines = source
+        .lines()
+        .map(|l| l.to_string())
+        .collect::<Vec<String>>();
+    match lines
+        .iter()
+        .rposition(|line| line.starts_with(prefix) && line.ends_with(';'))
+    {
+        Some(last_module) => lines.insert(last_module + 1, module),
+        None => {
+            let imports = lines
+                .iter()
+                .position(|line| line.starts_with("use "))
+                .ok_or("cannot find where to declare the module")?;
+            lines.splice(imports..imports, [module, String::new()]);
+        }
+    }
+
+    let start = format!("pub static {}", registry);
+    let registry_start = lines
+        .iter()
+        .position(|line| line.starts_with(&start))
+        .ok_or(format!("cannot find the `{}` registry", registry))?;
+    // rustfmt keeps short registries on a single line: spread them over one line per entry.
+    if let Some((head, entries)) = lines[registry_start]
+        .strip_suffix("];")
+        .and_then(|line| line.split_once("= &["))
+    {
+        let mut spread = vec![format!("{}= &[", head)];
+        spread.extend(split_entries(entries).map(|entry| format!("    {},", entry)));
+        spread.push("];".to_string());
+        lines.splice(registry_start..=registry_start, spread);
+    }
+    let registry_end = registry_start
+        + lines[registry_start..]
+            .iter()
+            .position(|line| line == "];")
+            .ok_or(format!(
+                "cannot find the end of the `{}` registry",
+                registry
+            ))?;
+    lines.insert(registry_end, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Declares `eN` and registers its solver in the registry `source` of `year`.
+fn register(source: &str, year: u32, day: u32) -> Result<String, String> {
+    let module = format!("pub mod e{};", day);
+    if source.lines().any(|line| line == module) {
+        return Err(format!("e{} is already declared", day));
+    }
+    declare(
+        source,
+        "pub mod e",
+        module,
+        "DAYS",
+        format!("    Day::new::<e{}::Solver>({}, {}),", day, year, day),
+    )
+}
+
+/// Declares `yYYYY` and registers its days in the `YEARS` registry `source`.
+fn register_year(source: &str, year: u32) -> Result<String, String> {
+    let module = format!("pub mod y{};", year);
+    if source.lines().any(|line| line == module) {
+        return Err(format!("y{} is already declared", year));
+    }
+    declare(
+        source,
+        "pub mod y",
+        module,
+        "YEARS",
+        format!("    Year::new({}, y{}::DAYS),", year, year),
+    )
+}
+
+/// Creates the module of `day` of `year` under the project `root` and its example fixture in
+/// `fixtures_dir`, and registers the day, along with the year if it is the first of its days.
+/// Returns the created files.
+pub fn scaffold(
+    root: &Path,
+    fixtures_dir: &str,
+    year: u32,
+    day: u32,
+) -> Result<Vec<PathBuf>, String> {
+    let module = root.join(format!("src/y{}/e{}/mod.rs", year, day));
+    if module.exists() {
+        return Err(format!("`{}` already exists", module.display()));
+    }
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|err| format!("cannot read `{}`: {}", path.display(), err))
+    };
+    let write = |path: &Path, content: &str| {
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(path, content))
+            .map_err(|err| format!("cannot write `{}`: {}", path.display(), err))
+    };
+
+    let mut created = vec![];
+    let registry_path = root.join(registry_path(year));
+    let years = match registry_path.exists() {
+        true => None,
+        false => {
+            let years_path = root.join(YEARS_FILE);
+            let years = register_year(&read(&years_path)?, year)?;
+            created.push(registry_path.clone());
+            Some((years_path, years))
+        }
+    };
+    let registry = match years {
+        Some(_) => YEAR_TEMPLATE.replace("{year}", &year.to_string()),
+        None => read(&registry_path)?,
+    };
+    let registry = register(&registry, year, day)?;
+
+    let fixture = root.join(fixture_path(fixtures_dir, year, day));
+    let answers = root.join(answers_path(fixtures_dir, year, day));
+    write(&module, &TEMPLATE.replace("{day}", &day.to_string()))?;
+    if !fixture.exists() {
+        write(&fixture, "")?;
+    }
+    if !answers.exists() {
+        write(&answers, ANSWERS_TEMPLATE)?;
+    }
+    write(&registry_path, &registry)?;
+    if let Some((years_path, years)) = years {
+        write(&years_path, &years)?;
+    }
+
+    created.extend([module, fixture, answers]);
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::fixtures::{parse_answers, FIXTURES_DIR};
+    use crate::tools::scratch_dir;
+
+    use super::{register, register_year, scaffold, YEAR_TEMPLATE};
+
+    static REGISTRY: &str = "//! Advent of Code 2023.
+
+pub mod e1;
+pub mod e2;
+
+use crate::solution::Day;
+
+pub static DAYS: &[Day] = &[
+    Day::new::<e1::Solver>(2023, 1),
+    Day::new::<e2::Solver>(2023, 2),
+];
+";
+
+    static YEARS: &str = "pub mod y2023;
+
+pub mod tools;
+
+pub use solution::{Day, Year};
+
+pub static YEARS: &[Year] = &[Year::new(2023, y2023::DAYS)];
+";
+
+    #[test]
+    fn register_day() {
+        assert_eq!(
+            register(REGISTRY, 2023, 3),
+            Ok("//! Advent of Code 2023.
+
+pub mod e1;
+pub mod e2;
+pub mod e3;
+
+use crate::solution::Day;
+
+pub static DAYS: &[Day] = &[
+    Day::new::<e1::Solver>(2023, 1),
+    Day::new::<e2::Solver>(2023, 2),
+    Day::new::<e3::Solver>(2023, 3),
+];
+"
+            .to_string())
+        );
+        assert_eq!(
+            register(REGISTRY, 2023, 2),
+            Err("e2 is already declared".to_string())
+        );
+        assert_eq!(
+            register(&YEAR_TEMPLATE.replace("{year}", "2024"), 2024, 1),
+            Ok("//! Advent of Code 2024.
+
+pub mod e1;
+
+use crate::solution::Day;
+
+pub static DAYS: &[Day] = &[
+    Day::new::<e1::Solver>(2024, 1),
+];
+"
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn register_years() {
+        assert_eq!(
+            register_year(YEARS, 2024),
+            Ok("pub mod y2023;
+pub mod y2024;
+
+pub mod tools;
+
+pub use solution::{Day, Year};
+
+pub static YEARS: &[Year] = &[
+    Year::new(2023, y2023::DAYS),
+    Year::new(2024, y2024::DAYS),
+];
+"
+            .to_string())
+        );
+        assert_eq!(
+            register_year(YEARS, 2023),
+            Err("y2023 is already declared".to_string())
+        );
+        assert_eq!(
+            register_year(
+                "pub mod y2023;\n\nuse solution::Year;\n\npub static YEARS: &[Year] = &[Year::new(2023, y2023::DAYS)];\n",
+                2024
+            ),
+            Ok("pub mod y2023;
+pub mod y2024;
+
+use solution::Year;
+
+pub static YEARS: &[Year] = &[
+    Year::new(2023, y2023::DAYS),
+    Year::new(2024, y2024::DAYS),
+];
+"
+            .to_string())
+        );
+
+        // The actual registries must stay in a shape the scaffold understands.
+        let understood = |result: Result<String, String>| match result {
+            Err(err) => !err.starts_with("cannot find"),
+            Ok(_) => true,
+        };
+        assert!(understood(register_year(include_str!("../lib.rs"), 2024)));
+        assert!(understood(register(
+            include_str!("../y2023/mod.rs"),
+            2023,
+            6
+        )));
+    }
+
+    #[test]
+    fn scaffold_day() {
+        let root = scratch_dir("scaffold");
+        fs::create_dir_all(root.join("src/y2023")).unwrap();
+        fs::write(root.join("src/lib.rs"), YEARS).unwrap();
+        fs::write(root.join("src/y2023/mod.rs"), REGISTRY).unwrap();
+
+        let created = scaffold(&root, FIXTURES_DIR, 2023, 3).unwrap();
+        assert_eq!(
+            created,
+            vec![
+                root.join("src/y2023/e3/mod.rs"),
+                root.join("fixtures/2023/e3/example.txt"),
+                root.join("fixtures/2023/e3/example.answers")
+            ]
+        );
+        let module = fs::read_to_string(root.join("src/y2023/e3/mod.rs")).unwrap();
+        assert!(module.contains("impl Solution for Solver"));
+        assert_eq!(
+            fs::read_to_string(root.join("fixtures/2023/e3/example.txt")).unwrap(),
+            ""
+        );
+        assert_eq!(
+            parse_answers(
+                &fs::read_to_string(root.join("fixtures/2023/e3/example.answers")).unwrap()
+            ),
+            Ok(vec![])
+        );
+        assert!(fs::read_to_string(root.join("src/y2023/mod.rs"))
+            .unwrap()
+            .contains("Day::new::<e3::Solver>(2023, 3),"));
+        assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), YEARS);
+
+        assert_eq!(
+            scaffold(&root, FIXTURES_DIR, 2023, 3),
+            Err(format!(
+                "`{}` already exists",
+                root.join("src/y2023/e3/mod.rs").display()
+            ))
+        );
+
+        let created = scaffold(&root, FIXTURES_DIR, 2024, 1).unwrap();
+        assert_eq!(created[0], root.join("src/y2024/mod.rs"));
+        assert!(fs::read_to_string(root.join("src/y2024/mod.rs"))
+            .unwrap()
+            .contains("Day::new::<e1::Solver>(2024, 1),"));
+        assert!(fs::read_to_string(root.join("src/lib.rs"))
+            .unwrap()
+            .contains("Year::new(2024, y2024::DAYS),"));
+    }
+}
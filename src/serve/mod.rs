@@ -0,0 +1,272 @@
+//! Local HTTP service solving the registered days, for those who would rather paste an input
+//! than build the crate: `POST /2023/day/5?part=2` with the input as body answers with the JSON
+//! document of the runner.
+//!
+//! Only what this needs of HTTP/1.1 is supported: one request per connection, with its body sized
+//! by `Content-Length`.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use crate::json::Value;
+use crate::runner::{self, DayOutcome};
+use crate::solution::Parts;
+use crate::{find_day, YEARS};
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:2023";
+
+/// Largest input accepted by default, well above any puzzle input.
+pub const DEFAULT_MAX_BODY: usize = 1 << 20;
+
+/// Largest request line and headers accepted.
+const MAX_HEAD: usize = 8 << 10;
+
+/// Time a client has to send its request.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most of a rejected request read after answering, so that the client gets to read the answer
+/// instead of a reset connection.
+const MAX_DRAINED: u64 = 64 << 20;
+
+#[derive(PartialEq, Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn error(status: u16, message: impl Into<String>) -> Response {
+        Response {
+            status,
+            body: Value::object([("error", Value::string(message))]),
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Content Too Large",
+        422 => "Unprocessable Content",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Lists the registered days.
+fn list_days() -> Response {
+    let days = YEARS
+        .iter()
+        .flat_map(|year| year.days)
+        .map(|day| {
+            Value::object([
+                ("year", Value::Number(day.year as u64)),
+                ("day", Value::Number(day.number as u64)),
+            ])
+        })
+        .collect();
+    Response {
+        status: 200,
+        body: Value::object([("days", Value::Array(days))]),
+    }
+}
+
+/// Answers a request for `target` (path and query) with the given `body`.
+pub fn route(method: &str, target: &str, body: &str) -> Response {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let segments = path.trim_matches('/').split('/').collect::<Vec<&str>>();
+    let (year, day) = match segments[..] {
+        [""] if method == "GET" => return list_days(),
+        [year, "day", day] => match (year.parse::<u32>(), day.parse::<u32>()) {
+            (Ok(year), Ok(day)) => (year, day),
+            _ => return Response::error(404, format!("no such page `{}`", path)),
+        },
+        _ => return Response::error(404, format!("no such page `{}`", path)),
+    };
+    if method != "POST" {
+        return Response::error(405, "post the puzzle input to solve it");
+    }
+    let day = match find_day(year, day) {
+        Ok(day) => day,
+        Err(message) => return Response::error(404, message),
+    };
+
+    let mut parts = Parts::Both;
+    for param in query.split('&').filter(|p| !p.is_empty()) {
+        parts = match param.split_once('=') {
+            Some(("part", "1")) => Parts::One,
+            Some(("part", "2")) => Parts::Two,
+            Some(("part", "both")) => Parts::Both,
+            _ => {
+                return Response::error(
+                    400,
+                    format!("invalid parameter `{}`, expected part=1, 2 or both", param),
+                )
+            }
+        };
+    }
+
+    let outcome = match runner::solve(day, "<body>", body, parts) {
+        Ok(report) => DayOutcome::Solved(report),
+        Err(err) => DayOutcome::Failed(err),
+    };
+    Response {
+        status: match outcome {
+            DayOutcome::Solved(_) => 200,
+            _ => 422,
+        },
+        body: runner::json_document(runner::json_results(day, &outcome)),
+    }
+}
+
+/// Reads a request off `stream`, returning its method, target and body.
+fn read_request(
+    reader: &mut BufReader<&TcpStream>,
+    max_body: usize,
+) -> Result<(String, String, String), Response> {
+    let mut head = String::new();
+    let mut length = None;
+    let mut request_line = None;
+    loop {
+        let mut line = String::new();
+        let read = reader
+            .by_ref()
+            .take((MAX_HEAD + 1 - head.len().min(MAX_HEAD)) as u64)
+            .read_line(&mut line);
+        match read {
+            Ok(0) => return Err(Response::error(400, "incomplete request")),
+            Ok(_) => (),
+            // Timeouts are reported as either kind, depending on the platform.
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                return Err(Response::error(408, "the request took too long"))
+            }
+            Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                return Err(Response::error(400, "the request is not valid UTF-8"))
+            }
+            Err(err) => {
+                return Err(Response::error(
+                    400,
+                    format!("cannot read the request: {}", err),
+                ))
+            }
+        }
+        head.push_str(&line);
+        if head.len() > MAX_HEAD {
+            return Err(Response::error(
+                431,
+                format!("headers are limited to {} bytes", MAX_HEAD),
+            ));
+        }
+        let line = line.trim_end();
+        if request_line.is_none() {
+            request_line = Some(line.to_string());
+            continue;
+        }
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(Response::error(400, format!("invalid header `{}`", line)));
+        };
+        if name.trim().eq_ignore_ascii_case("Content-Length") {
+            length = Some(value.trim().parse::<usize>().map_err(|_| {
+                Response::error(400, format!("invalid Content-Length `{}`", value.trim()))
+            })?);
+        }
+    }
+
+    let request_line = request_line.unwrap_or_default();
+    let mut words = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (words.next(), words.next()) else {
+        return Err(Response::error(400, "invalid request line"));
+    };
+    let length = match (method, length) {
+        ("POST", None) => return Err(Response::error(411, "Content-Length is required")),
+        (_, length) => length.unwrap_or(0),
+    };
+    if length > max_body {
+        return Err(Response::error(
+            413,
+            format!("inputs are limited to {} bytes", max_body),
+        ));
+    }
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| Response::error(400, "incomplete request"))?;
+    let body = String::from_utf8(body)
+        .map_err(|_| Response::error(400, "the input is not valid UTF-8"))?;
+    Ok((method.to_string(), target.to_string(), body))
+}
+
+fn handle(stream: TcpStream, max_body: usize) -> io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let (response, rejected) = match read_request(&mut reader, max_body) {
+        Ok((method, target, body)) => (route(&method, &target, &body), false),
+        Err(response) => (response, true),
+    };
+    let body = response.body.to_string();
+    write!(
+        &stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        body.len(),
+        body
+    )?;
+    if rejected {
+        stream.shutdown(Shutdown::Write)?;
+        io::copy(&mut reader.take(MAX_DRAINED), &mut io::sink())?;
+    }
+    Ok(())
+}
+
+/// Answers the connections to `listener`, each on its own thread, with bodies of up to
+/// `max_body` bytes.
+pub fn serve(listener: TcpListener, max_body: usize) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        thread::spawn(move || {
+            // The client went away: there is no one left to tell.
+            let _ = handle(stream, max_body);
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::route;
+
+    #[test]
+    fn route_requests() {
+        let example = include_str!("../../fixtures/2023/e2/example.txt");
+        let response = route("POST", "/2023/day/2?part=1", example);
+        assert_eq!(response.status, 200);
+        assert!(response
+            .body
+            .to_string()
+            .contains(r#""day":2,"part":1,"answer":"8""#));
+
+        let status = |method, target, body| route(method, target, body).status;
+        assert_eq!(status("POST", "/2023/day/2", "Game 1: 3 purple"), 422);
+        assert_eq!(status("POST", "/2023/day/2?part=3", example), 400);
+        assert_eq!(status("GET", "/2023/day/2", ""), 405);
+        assert_eq!(status("POST", "/2023/day/42", example), 404);
+        assert_eq!(status("POST", "/2023/day/e2", example), 404);
+        assert_eq!(status("GET", "/", ""), 200);
+    }
+}
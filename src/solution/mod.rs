@@ -0,0 +1,195 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use crate::error::Result;
+use crate::memory::{self, Usage};
+use crate::repl::{self, Command, Session};
+use crate::trace::{self, timed};
+
+/// A puzzle of the calendar: the input is parsed once, then both parts are solved on it.
+pub trait Solution {
+    type Input: 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(source: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+    /// Commands exploring the parsed input in the REPL, besides solving the parts.
+    fn commands() -> Vec<Command<Self::Input>> {
+        vec![]
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: String,
+    pub time: Duration,
+    /// Heap usage of the part, when counted.
+    pub memory: Option<Usage>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Report {
+    pub parse_time: Duration,
+    /// Heap usage of the whole day, parsing included, when counted.
+    pub memory: Option<Usage>,
+    pub parts: Vec<PartReport>,
+}
+
+/// Which parts of a day should be solved.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Parts {
+    One,
+    Two,
+    Both,
+}
+
+impl Parts {
+    pub fn includes(self, part: u8) -> bool {
+        matches!(
+            (self, part),
+            (Parts::One, 1) | (Parts::Two, 2) | (Parts::Both, _)
+        )
+    }
+}
+
+/// Default location of the puzzle input of `day` of `year`.
+pub fn input_path(year: u32, day: u32) -> String {
+    format!("{}/{}_input.txt", year, day)
+}
+
+/// The registered days of an event.
+pub struct Year {
+    pub number: u32,
+    pub days: &'static [Day],
+}
+
+impl Year {
+    pub const fn new(number: u32, days: &'static [Day]) -> Year {
+        Year { number, days }
+    }
+}
+
+/// A registered day, with its `Solution` erased so that days can live in the same list.
+pub struct Day {
+    pub year: u32,
+    pub number: u32,
+    solve: fn(&str, Parts) -> Result<Report>,
+    explore: fn(&str) -> Result<Box<dyn Session>>,
+}
+
+impl Day {
+    pub const fn new<S: Solution + 'static>(year: u32, number: u32) -> Day {
+        Day {
+            year,
+            number,
+            solve: solve::<S>,
+            explore: repl::open::<S>,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        format!("e{}", self.number)
+    }
+
+    pub fn input_path(&self) -> String {
+        input_path(self.year, self.number)
+    }
+
+    pub fn solve(&self, source: &str, parts: Parts) -> Result<Report> {
+        let _span = trace::span(self.name());
+        let (report, memory) = memory::measure(|| (self.solve)(source, parts));
+        Ok(Report { memory, ..report? })
+    }
+
+    /// Parses `source` once, for a REPL session.
+    pub fn explore(&self, source: &str) -> Result<Box<dyn Session>> {
+        (self.explore)(source)
+    }
+}
+
+fn solve<S: Solution>(source: &str, parts: Parts) -> Result<Report> {
+    let (input, parse_time) = timed("parse", || S::parse(source));
+    let input = input?;
+    let mut reports = vec![];
+    if parts.includes(1) {
+        let ((answer, time), memory) = memory::measure(|| timed("part1", || S::part1(&input)));
+        reports.push(PartReport {
+            part: 1,
+            answer: answer?.to_string(),
+            time,
+            memory,
+        });
+    }
+    if parts.includes(2) {
+        let ((answer, time), memory) = memory::measure(|| timed("part2", || S::part2(&input)));
+        reports.push(PartReport {
+            part: 2,
+            answer: answer?.to_string(),
+            time,
+            memory,
+        });
+    }
+    Ok(Report {
+        parse_time,
+        memory: None,
+        parts: reports,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{Error, Result};
+
+    use super::{Day, Parts, Solution};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = usize;
+
+        fn parse(source: &str) -> Result<Vec<u32>> {
+            source
+                .split(',')
+                .map(|x| {
+                    x.parse()
+                        .map_err(|_| Error::parse_in(source, x, "not a number"))
+                })
+                .collect()
+        }
+        fn part1(input: &Vec<u32>) -> Result<u32> {
+            Ok(input.iter().sum())
+        }
+        fn part2(input: &Vec<u32>) -> Result<usize> {
+            Ok(input.len())
+        }
+    }
+
+    #[test]
+    fn erased_day() {
+        let day = Day::new::<Sum>(2023, 42);
+        assert_eq!(day.name(), "e42");
+        assert_eq!(day.input_path(), "2023/42_input.txt");
+        let report = day.solve("1,2,3", Parts::Both).unwrap();
+        assert_eq!(
+            report
+                .parts
+                .iter()
+                .map(|p| (p.part, p.answer.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, "6"), (2, "3")]
+        );
+        let report = day.solve("1,2,3", Parts::Two).unwrap();
+        assert_eq!(report.parts.len(), 1);
+        assert_eq!(report.parts[0].part, 2);
+        assert_eq!(
+            day.solve("1,x", Parts::Both),
+            Err(Error::parse(3, "x", "not a number"))
+        );
+    }
+}
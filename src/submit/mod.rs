@@ -0,0 +1,462 @@
+//! Answer submission, with a local history of attempts so that an answer already known to be
+//! wrong is never sent twice.
+//!
+//! The history file holds one `<unix time> <day> <part> <verdict> <wait> <answer>` line per
+//! attempt, where `<wait>` is the number of seconds the site asked to wait before the next try.
+
+use std::fmt::{self, Display};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use regex::Regex;
+
+use crate::client::Client;
+use crate::error::Error;
+
+/// Location of the history of the attempts of `year`.
+pub fn history_path(year: u32) -> String {
+    format!("{}/submissions.txt", year)
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    Wait,
+    AlreadySolved,
+}
+
+impl Verdict {
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::Wait => "wait",
+            Verdict::AlreadySolved => "already-solved",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        [
+            Verdict::Correct,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Incorrect,
+            Verdict::Wait,
+            Verdict::AlreadySolved,
+        ]
+        .into_iter()
+        .find(|v| v.name() == name)
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "Correct",
+            Verdict::TooHigh => "Too high",
+            Verdict::TooLow => "Too low",
+            Verdict::Incorrect => "Incorrect",
+            Verdict::Wait => "Wait",
+            Verdict::AlreadySolved => "Already solved",
+        })
+    }
+}
+
+/// Reads the verdict out of the page returned after posting an answer, along with the number of
+/// seconds to wait before the next attempt.
+pub fn parse_response(html: &str) -> Result<(Verdict, u64), String> {
+    let left_to_wait =
+        Regex::new(r"You have (?:(?<m>[0-9]+)m )?(?<s>[0-9]+)s left to wait").unwrap();
+    let wait_before_retry =
+        Regex::new(r"(?i)please wait (?<n>one|[0-9]+) minutes? before").unwrap();
+
+    if let Some(cap) = left_to_wait.captures(html) {
+        let minutes = cap
+            .name("m")
+            .map_or(0, |m| m.as_str().parse::<u64>().unwrap());
+        let seconds = cap.name("s").unwrap().as_str().parse::<u64>().unwrap();
+        return Ok((Verdict::Wait, minutes * 60 + seconds));
+    }
+    if html.contains("That's the right answer") {
+        return Ok((Verdict::Correct, 0));
+    }
+    if html.contains("Did you already complete it?") {
+        return Ok((Verdict::AlreadySolved, 0));
+    }
+    if html.contains("That's not the right answer") {
+        let wait = match wait_before_retry
+            .captures(html)
+            .map(|cap| cap.name("n").unwrap())
+        {
+            Some(n) if n.as_str() == "one" => 60,
+            Some(n) => n.as_str().parse::<u64>().unwrap() * 60,
+            None => 0,
+        };
+        let verdict = if html.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if html.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        };
+        return Ok((verdict, wait));
+    }
+    Err("unrecognized response page".to_string())
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Attempt {
+    pub at: u64,
+    pub day: u32,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub wait: u64,
+    pub answer: String,
+}
+
+impl Attempt {
+    fn from_line(line: &str) -> Option<Attempt> {
+        let mut sp = line.split_whitespace();
+        Some(Attempt {
+            at: sp.next()?.parse().ok()?,
+            day: sp.next()?.strip_prefix('e')?.parse().ok()?,
+            part: sp.next()?.parse().ok()?,
+            verdict: Verdict::from_name(sp.next()?)?,
+            wait: sp.next()?.parse().ok()?,
+            answer: sp.next()?.to_string(),
+        })
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{} e{} {} {} {} {}",
+            self.at,
+            self.day,
+            self.part,
+            self.verdict.name(),
+            self.wait,
+            self.answer
+        )
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct History {
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn parse(source: &str) -> Result<History, Error> {
+        let mut attempts = vec![];
+        for (idx, line) in source.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let attempt = Attempt::from_line(line).ok_or(
+                Error::parse(
+                    1,
+                    line,
+                    "expected `<time> <day> <part> <verdict> <wait> <answer>`",
+                )
+                .on_line(idx + 1),
+            )?;
+            attempts.push(attempt);
+        }
+        Ok(History { attempts })
+    }
+
+    /// Loads the history at `path`, which is empty when nothing was submitted yet.
+    pub fn load(path: &str) -> Result<History, String> {
+        match fs::read_to_string(path) {
+            Ok(source) => History::parse(&source).map_err(|err| err.in_file(path).to_string()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(Error::io(path, err).to_string()),
+        }
+    }
+
+    pub fn append(&mut self, path: &str, attempt: Attempt) -> Result<(), String> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("cannot create `{}`: {}", parent.display(), err))?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", attempt.to_line()))
+            .map_err(|err| format!("cannot write `{}`: {}", path, err))?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    /// Why `answer` must not be submitted at time `now`, if the history already tells.
+    pub fn refusal(&self, day: u32, part: u8, answer: &str, now: u64) -> Option<String> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|a| a.day == day && a.part == part)
+            .collect::<Vec<_>>();
+
+        if let Some(last) = attempts.last() {
+            if last.at + last.wait > now {
+                return Some(format!(
+                    "wait {}s before submitting e{} part {} again",
+                    last.at + last.wait - now,
+                    day,
+                    part
+                ));
+            }
+        }
+
+        let value = answer.parse::<i64>().ok();
+        for attempt in attempts {
+            match attempt.verdict {
+                Verdict::Correct | Verdict::AlreadySolved => {
+                    return Some(format!("e{} part {} is already solved", day, part))
+                }
+                verdict if verdict.is_wrong() && attempt.answer == answer => {
+                    return Some(format!("`{}` was already rejected ({})", answer, verdict))
+                }
+                _ => {}
+            }
+            let known = attempt.answer.parse::<i64>().ok();
+            match (attempt.verdict, value, known) {
+                (Verdict::TooHigh, Some(value), Some(known)) if value >= known => {
+                    return Some(format!("`{}` is too high, `{}` already was", answer, known))
+                }
+                (Verdict::TooLow, Some(value), Some(known)) if value <= known => {
+                    return Some(format!("`{}` is too low, `{}` already was", answer, known))
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Submits `answer` unless the history in `history_path` shows it cannot be right, and records
+/// the attempt.
+pub fn submit(
+    client: &Client,
+    history_path: &str,
+    year: u32,
+    day: u32,
+    part: u8,
+    answer: &str,
+) -> Result<(Verdict, u64), String> {
+    // The history could not be read back with an answer of several words, or none.
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(format!(
+            "invalid answer `{}`, expected a single word",
+            answer
+        ));
+    }
+    let mut history = History::load(history_path)?;
+    let at = now();
+    if let Some(reason) = history.refusal(day, part, answer, at) {
+        return Err(format!("not submitting: {}", reason));
+    }
+    let (verdict, wait) = parse_response(&client.submit(year, day, part, answer)?)?;
+    history.append(
+        history_path,
+        Attempt {
+            at,
+            day,
+            part,
+            verdict,
+            wait,
+            answer: answer.to_string(),
+        },
+    )?;
+    Ok((verdict, wait))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::client::Client;
+    use crate::tools::scratch_dir;
+    use crate::tools::stub::StubServer;
+
+    use super::{parse_response, submit, Attempt, History, Verdict};
+
+    fn page(message: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    #[test]
+    fn parse_verdicts() {
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer! You are one gold star closer."
+            )),
+            Ok((Verdict::Correct, 0))
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer; your answer is too high. Please wait one minute before trying again.")),
+            Ok((Verdict::TooHigh, 60))
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer; your answer is too low. Please wait 5 minutes before trying again.")),
+            Ok((Verdict::TooLow, 300))
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer. If you're stuck, make sure you're using the full input data.")),
+            Ok((Verdict::Incorrect, 0))
+        );
+        assert_eq!(
+            parse_response(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 12s left to wait.")),
+            Ok((Verdict::Wait, 72))
+        );
+        assert_eq!(
+            parse_response(&page("You have 37s left to wait.")),
+            Ok((Verdict::Wait, 37))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Ok((Verdict::AlreadySolved, 0))
+        );
+        assert!(parse_response(&page("Something else")).is_err());
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let attempt = Attempt {
+            at: 1701763200,
+            day: 5,
+            part: 2,
+            verdict: Verdict::TooHigh,
+            wait: 60,
+            answer: "12345".to_string(),
+        };
+        assert_eq!(attempt.to_line(), "1701763200 e5 2 too-high 60 12345");
+        assert_eq!(
+            History::parse(&attempt.to_line()),
+            Ok(History {
+                attempts: vec![attempt]
+            })
+        );
+        assert_eq!(
+            History::parse("1701763200 e5 2 maybe 0 1")
+                .map_err(|err| err.in_file("submissions.txt").to_string()),
+            Err(
+                "submissions.txt:1:1: expected `<time> <day> <part> <verdict> <wait> <answer>`"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn refusals() {
+        let history = History::parse(
+            "1000 e5 2 too-high 60 500
+1100 e5 2 too-low 60 100
+1200 e4 1 correct 0 13
+1300 e3 1 wait 30 4361",
+        )
+        .unwrap();
+        assert_eq!(history.refusal(5, 2, "300", 1200), None);
+        assert_eq!(
+            history.refusal(5, 2, "300", 1150),
+            Some("wait 10s before submitting e5 part 2 again".to_string())
+        );
+        assert_eq!(
+            history.refusal(5, 2, "500", 1200),
+            Some("`500` was already rejected (Too high)".to_string())
+        );
+        assert_eq!(
+            history.refusal(5, 2, "600", 1200),
+            Some("`600` is too high, `500` already was".to_string())
+        );
+        assert_eq!(
+            history.refusal(5, 2, "50", 1200),
+            Some("`50` is too low, `100` already was".to_string())
+        );
+        assert_eq!(
+            history.refusal(4, 1, "14", 1300),
+            Some("e4 part 1 is already solved".to_string())
+        );
+        assert_eq!(history.refusal(3, 1, "4361", 1400), None);
+        assert_eq!(history.refusal(5, 1, "500", 1200), None);
+    }
+
+    #[test]
+    fn submit_records_attempts() {
+        let server = StubServer::start(|request| match request.body.as_str() {
+            "level=1&answer=35" => (200, page("That's the right answer!")),
+            _ => (
+                200,
+                page("That's not the right answer; your answer is too high."),
+            ),
+        });
+        let client = Client::with_base_url(&server.url, "s3cr3t".to_string());
+        let history = scratch_dir("submit").join("submissions.txt");
+        let history = history.to_str().unwrap();
+
+        assert_eq!(
+            submit(&client, history, 2023, 5, 1, "99"),
+            Ok((Verdict::TooHigh, 0))
+        );
+        assert!(submit(&client, history, 2023, 5, 1, "99").is_err());
+        assert_eq!(
+            submit(&client, history, 2023, 5, 1, "4 2"),
+            Err("invalid answer `4 2`, expected a single word".to_string())
+        );
+        assert!(submit(&client, history, 2023, 5, 1, "").is_err());
+        assert_eq!(
+            submit(&client, history, 2023, 5, 1, "35"),
+            Ok((Verdict::Correct, 0))
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2023/day/5/answer");
+        assert_eq!(requests[0].header("Cookie"), Some("session=s3cr3t"));
+        assert_eq!(
+            requests[0].header("Content-Type"),
+            Some("application/x-www-form-urlencoded")
+        );
+        assert_eq!(fs::read_to_string(history).unwrap().lines().count(), 2);
+
+        // An unreadable history must not pass for an empty one.
+        fs::write(history, b"1000 e5 1 too-high 0 \xff\n").unwrap();
+        assert_eq!(
+            submit(&client, history, 2023, 5, 1, "99"),
+            Err(format!(
+                "cannot read `{}`: stream did not contain valid UTF-8",
+                history
+            ))
+        );
+        fs::write(history, "1000 e5 1 too-high\n").unwrap();
+        assert!(submit(&client, history, 2023, 5, 1, "99").is_err());
+        assert_eq!(server.requests().len(), 2);
+    }
+}
@@ -1,13 +1,30 @@
 use std::fs::read_to_string;
 
-pub fn vec_lines(file_path: &str) -> Vec<String> {
-    let mut result = Vec::new();
+use crate::error::{Error, Result};
 
-    for line in read_to_string(file_path).unwrap().lines() {
-        if !line.is_empty() {
-            result.push(line.to_string())
-        }
-    }
+/// Every line of `source`, blank ones included so that parsers can report line numbers.
+pub fn lines(source: &str) -> Vec<String> {
+    source.lines().map(|line| line.to_string()).collect()
+}
+
+/// Non-blank lines of the file at `file_path`.
+pub fn vec_lines(file_path: &str) -> Result<Vec<String>> {
+    Ok(read_to_string(file_path)
+        .map_err(|err| Error::io(file_path, err))?
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect())
+}
+
+#[cfg(test)]
+pub mod stub;
 
-    result
+/// Fresh empty directory under the system temp dir, for tests touching the filesystem.
+#[cfg(test)]
+pub fn scratch_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc2023-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
 }
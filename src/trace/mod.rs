@@ -0,0 +1,250 @@
+//! Spans timing the phases of a solution, reported on stderr while they close and optionally
+//! recorded as a Chrome trace, to be opened in `chrome://tracing` or Perfetto.
+//!
+//! Spans cost a single atomic load when tracing is disabled, so they can stay in the solutions.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fs;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+use crate::json::Value;
+use crate::runner::format_duration;
+
+/// How much is reported on stderr.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Default)]
+pub enum Level {
+    #[default]
+    Off,
+    /// Parsing and each part of every day.
+    Verbose,
+    /// Inner phases of the solutions too, along with their events.
+    Trace,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static EVENTS: Mutex<Vec<Value>> = Mutex::new(vec![]);
+static START: OnceLock<Instant> = OnceLock::new();
+static THREADS: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Names of the spans open on this thread, outermost first.
+    static STACK: RefCell<Vec<Cow<'static, str>>> = const { RefCell::new(vec![]) };
+    static THREAD: u64 = THREADS.fetch_add(1, Ordering::Relaxed) + 1;
+}
+
+/// Reports the spans up to `level` on stderr, and records all of them when `record` is set.
+pub fn init(level: Level, record: bool) {
+    START.get_or_init(Instant::now);
+    LEVEL.store(level as u8, Ordering::Relaxed);
+    RECORDING.store(record, Ordering::Relaxed);
+}
+
+fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8 || RECORDING.load(Ordering::Relaxed)
+}
+
+fn reported(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+fn micros(instant: Instant) -> u64 {
+    instant
+        .duration_since(*START.get_or_init(Instant::now))
+        .as_micros() as u64
+}
+
+/// Line reported on stderr, `path` being the names of the open spans.
+fn line(path: &[Cow<str>], message: &str) -> String {
+    format!("[trace] {}: {}", path.join(" > "), message)
+}
+
+/// Chrome trace event of the given phase type (`X` for a span, `i` for an instant event).
+fn chrome_event(phase: &str, name: &str, detail: Option<&str>, ts: u64, dur: Option<u64>) -> Value {
+    let mut members = vec![
+        ("name", Value::string(name)),
+        ("cat", Value::string("aoc")),
+        ("ph", Value::string(phase)),
+        ("ts", Value::Number(ts)),
+    ];
+    match dur {
+        Some(dur) => members.push(("dur", Value::Number(dur))),
+        None => members.push(("s", Value::string("t"))),
+    }
+    members.push(("pid", Value::Number(1)));
+    members.push(("tid", Value::Number(THREAD.with(|id| *id))));
+    if let Some(detail) = detail {
+        members.push(("args", Value::object([("detail", Value::string(detail))])));
+    }
+    Value::object(members)
+}
+
+/// A timed phase, closed when dropped.
+pub struct Span {
+    level: Level,
+    detail: Option<String>,
+    /// `None` when tracing is disabled for the level of the span.
+    start: Option<Instant>,
+}
+
+impl Span {
+    fn open(
+        name: impl Into<Cow<'static, str>>,
+        level: Level,
+        detail: impl FnOnce() -> Option<String>,
+    ) -> Span {
+        if !enabled(level) {
+            return Span {
+                level,
+                detail: None,
+                start: None,
+            };
+        }
+        STACK.with(|stack| stack.borrow_mut().push(name.into()));
+        Span {
+            level,
+            detail: detail(),
+            start: Some(Instant::now()),
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some(start) = self.start else { return };
+        let elapsed = start.elapsed();
+        let name = STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            if reported(self.level) {
+                let message = match &self.detail {
+                    Some(detail) => format!("{} ({})", format_duration(elapsed), detail),
+                    None => format_duration(elapsed),
+                };
+                eprintln!("{}", line(&stack, &message));
+            }
+            stack.pop().unwrap_or_default()
+        });
+        if RECORDING.load(Ordering::Relaxed) {
+            let event = chrome_event(
+                "X",
+                &name,
+                self.detail.as_deref(),
+                micros(start),
+                Some(elapsed.as_micros() as u64),
+            );
+            EVENTS.lock().unwrap().push(event);
+        }
+    }
+}
+
+/// Span of a phase reported with `--verbose`, such as parsing or solving a part.
+pub fn span(name: impl Into<Cow<'static, str>>) -> Span {
+    Span::open(name, Level::Verbose, || None)
+}
+
+/// Span of an inner phase reported with `--trace`, along with the `detail` it is given.
+pub fn span_with(name: &'static str, detail: impl FnOnce() -> String) -> Span {
+    Span::open(name, Level::Trace, || Some(detail()))
+}
+
+/// Reports `message` with `--trace`, within the spans open on this thread.
+pub fn event(message: impl FnOnce() -> String) {
+    if !enabled(Level::Trace) {
+        return;
+    }
+    let message = message();
+    if reported(Level::Trace) {
+        STACK.with(|stack| eprintln!("{}", line(&stack.borrow(), &message)));
+    }
+    if RECORDING.load(Ordering::Relaxed) {
+        let event = chrome_event("i", &message, None, micros(Instant::now()), None);
+        EVENTS.lock().unwrap().push(event);
+    }
+}
+
+/// Runs `f` within the span `name`, returning its result along with its duration.
+pub fn timed<T>(name: &'static str, f: impl FnOnce() -> T) -> (T, Duration) {
+    let _span = span(name);
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Everything recorded since [`init`], in the Chrome trace event format.
+pub fn chrome_trace() -> Value {
+    Value::object([
+        ("traceEvents", Value::Array(EVENTS.lock().unwrap().clone())),
+        ("displayTimeUnit", Value::string("ms")),
+    ])
+}
+
+pub fn write_chrome_trace(path: &str) -> Result<()> {
+    fs::write(path, chrome_trace().to_string()).map_err(|err| Error::io(path, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json::Value;
+
+    use super::{chrome_trace, event, init, line, span, span_with, Level, THREAD};
+
+    #[test]
+    fn record_spans() {
+        assert_eq!(
+            line(&["e5".into(), "part2".into()], "1.000ms"),
+            "[trace] e5 > part2: 1.000ms"
+        );
+
+        init(Level::Off, true);
+        {
+            let _day = span("e42");
+            let _range = span_with("range", || "79..93".to_string());
+            event(|| "seed 80".to_string());
+        }
+        let tid = Value::Number(THREAD.with(|id| *id));
+        let Value::Object(trace) = chrome_trace() else {
+            panic!("not an object")
+        };
+        let Value::Array(events) = &trace[0].1 else {
+            panic!("no events")
+        };
+        // Other tests may record spans of their own in the meantime, on other threads.
+        let mine = events
+            .iter()
+            .filter_map(|event| match event {
+                Value::Object(members) if members.contains(&("tid".to_string(), tid.clone())) => {
+                    Some(members)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let member = |members: &Vec<(String, Value)>, name: &str| {
+            members
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, value)| value.to_string())
+        };
+        assert_eq!(
+            mine.iter()
+                .map(|members| (
+                    member(members, "name").unwrap(),
+                    member(members, "ph").unwrap(),
+                    member(members, "args")
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("\"seed 80\"".to_string(), "\"i\"".to_string(), None),
+                (
+                    "\"range\"".to_string(),
+                    "\"X\"".to_string(),
+                    Some(r#"{"detail":"79..93"}"#.to_string())
+                ),
+                ("\"e42\"".to_string(), "\"X\"".to_string(), None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Re-running a day whenever its input or examples change, while iterating on a solution.
+//!
+//! Files are polled for their modification time, which needs no platform-specific API. Changes
+//! to the sources are not picked up, since they need a rebuild: run under `cargo watch` for that.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::error::Error;
+use crate::fixtures;
+use crate::runner;
+use crate::solution::{Day, Parts};
+
+pub const INTERVAL: Duration = Duration::from_millis(500);
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Modification times of `paths`, and of the files directly in those which are directories.
+/// Missing paths are left out, so that creating them counts as a change.
+pub fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, SystemTime> {
+    let mut times = BTreeMap::new();
+    let mut record = |path: &Path| {
+        if let Ok(time) = fs::metadata(path).and_then(|m| m.modified()) {
+            times.insert(path.to_path_buf(), time);
+        }
+    };
+    for path in paths {
+        record(path);
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                record(&entry.path());
+            }
+        }
+    }
+    times
+}
+
+/// One line per example of `day` under `dir`, telling whether it still passes.
+pub fn example_lines(day: &Day, dir: &Path) -> Vec<String> {
+    let found = match fixtures::discover(dir) {
+        Ok(found) => found,
+        Err(Error::Io { source, .. }) if source.kind() == ErrorKind::NotFound => vec![],
+        Err(err) => return vec![err.to_string()],
+    };
+    let mut lines = vec![];
+    for fixture in found
+        .iter()
+        .filter(|f| f.year == day.year && f.day == day.number)
+    {
+        match fixture.check(day) {
+            Ok(checks) if checks.is_empty() => {
+                lines.push(format!("{}: no expected answer", fixture.name))
+            }
+            Ok(checks) => lines.extend(checks.iter().map(|check| match check.passed() {
+                true => format!("{} part {}: ok", fixture.name, check.part),
+                false => format!(
+                    "{} part {}: FAILED, expected {}, got {}",
+                    fixture.name, check.part, check.expected, check.actual
+                ),
+            })),
+            Err(err) => lines.push(format!("{}: {}", fixture.name, err)),
+        }
+    }
+    if lines.is_empty() {
+        lines.push(format!(
+            "no example in {}/{}/{}",
+            dir.display(),
+            day.year,
+            day.name()
+        ));
+    }
+    lines
+}
+
+fn refresh(day: &Day, path: &str, fixtures_dir: &Path, parts: Parts) {
+    print!("{}", CLEAR_SCREEN);
+    println!("Watching {} and its examples (Ctrl-C to stop)\n", path);
+    if let Err(err) =
+        runner::read_input(path).and_then(|source| runner::run(day, path, &source, parts))
+    {
+        println!("error: {}", err);
+    }
+    println!("\nExamples:");
+    for line in example_lines(day, fixtures_dir) {
+        println!("  {}", line);
+    }
+}
+
+/// Runs `day` on the input at `path`, then again on every change to it or to the examples under
+/// `fixtures_dir`.
+pub fn watch(day: &Day, path: &str, fixtures_dir: &Path, parts: Parts) -> ! {
+    let watched = vec![
+        PathBuf::from(path),
+        fixtures_dir.join(day.year.to_string()).join(day.name()),
+    ];
+    let mut last = None;
+    loop {
+        let current = snapshot(&watched);
+        if last.as_ref() != Some(&current) {
+            refresh(day, path, fixtures_dir, parts);
+            last = Some(current);
+        }
+        thread::sleep(INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::time::{Duration, SystemTime};
+
+    use crate::tools::scratch_dir;
+    use crate::y2023::DAYS;
+
+    use super::{example_lines, snapshot};
+
+    #[test]
+    fn detect_changes() {
+        let root = scratch_dir("watch");
+        let (input, examples) = (root.join("1_input.txt"), root.join("e1"));
+        fs::create_dir_all(&examples).unwrap();
+        let watched = vec![input.clone(), examples.clone()];
+
+        let before = snapshot(&watched);
+        assert_eq!(before.keys().collect::<Vec<_>>(), vec![&examples]);
+
+        fs::write(&input, "1abc2\n").unwrap();
+        fs::write(examples.join("example.txt"), "1abc2\n").unwrap();
+        let after = snapshot(&watched);
+        assert_eq!(after.len(), 3);
+        assert_eq!(snapshot(&watched), after);
+
+        let later = SystemTime::now() + Duration::from_secs(5);
+        File::options()
+            .write(true)
+            .open(&input)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(snapshot(&watched)[&input], later);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn report_examples() {
+        let root = scratch_dir("watch-examples");
+        assert_eq!(
+            example_lines(&DAYS[0], &root),
+            vec![format!("no example in {}/2023/e1", root.display())]
+        );
+
+        fs::create_dir_all(root.join("2023/e1")).unwrap();
+        fs::write(root.join("2023/e1/a.txt"), "1abc2\n").unwrap();
+        fs::write(root.join("2023/e1/a.answers"), "part1: 12\npart2: 21\n").unwrap();
+        fs::write(root.join("2023/e1/b.txt"), "").unwrap();
+        assert_eq!(
+            example_lines(&DAYS[0], &root),
+            vec![
+                "a part 1: ok",
+                "a part 2: FAILED, expected 21, got 12",
+                "b: no expected answer"
+            ]
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,172 @@
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::tools::lines;
+use regex::Regex;
+
+pub fn convert(source: &str) -> u32 {
+    match source {
+        "1" => 1,
+        "2" => 2,
+        "3" => 3,
+        "4" => 4,
+        "5" => 5,
+        "6" => 6,
+        "7" => 7,
+        "8" => 8,
+        "9" => 9,
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        "eno" => 1,
+        "owt" => 2,
+        "eerht" => 3,
+        "ruof" => 4,
+        "evif" => 5,
+        "xis" => 6,
+        "neves" => 7,
+        "thgie" => 8,
+        "enin" => 9,
+        _ => 0,
+    }
+}
+
+pub fn extract_v2(source: String) -> Result<u32> {
+    let exp = Regex::new(r"(one|two|three|four|five|six|seven|eight|nine|[0-9])").unwrap();
+    let exp_reverse = Regex::new(r"(eno|owt|eerht|ruof|evif|xis|neves|thgie|enin|[0-9])").unwrap();
+    let caps = exp.captures_iter(source.as_str());
+    let rev_source = source.chars().rev().collect::<String>();
+    let caps_reverse = exp_reverse.captures_iter(rev_source.as_str());
+    let coll_ord: Vec<u32> = caps
+        .map(|c| {
+            let (digit, _): (&str, [&str; 1]) = c.extract();
+            convert(digit)
+        })
+        .collect();
+    let coll_rev: Vec<u32> = caps_reverse
+        .map(|c| {
+            let (digit, _): (&str, [&str; 1]) = c.extract();
+            convert(digit)
+        })
+        .collect();
+    match (coll_ord.first(), coll_rev.first()) {
+        (Some(first), Some(last)) => Ok(first * 10 + last),
+        _ => Err(Error::parse(
+            1,
+            &source,
+            format!("expected a digit or a spelled digit, got \"{}\"", source),
+        )),
+    }
+}
+
+pub fn extract_v1(source: String) -> Result<u32> {
+    let digits: Vec<u32> = source
+        .chars()
+        .filter_map(|c| {
+            if c.is_ascii_digit() {
+                Some(u32::from(c) - 48)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    match (digits.first(), digits.last()) {
+        (Some(first), Some(last)) => Ok(first * 10 + last),
+        _ => Err(Error::parse(
+            1,
+            &source,
+            format!("expected a digit, got \"{}\"", source),
+        )),
+    }
+}
+
+fn sum_with(source: &[String], extract: fn(String) -> Result<u32>) -> Result<u32> {
+    source
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| extract(line.into()).map_err(|err| err.on_line(idx + 1)))
+        .sum()
+}
+
+pub fn sum_on_text(source: Vec<String>) -> Result<u32> {
+    sum_with(&source, extract_v2)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(source: &str) -> Result<Vec<String>> {
+        Ok(lines(source))
+    }
+
+    fn part1(input: &Vec<String>) -> Result<u32> {
+        sum_with(input, extract_v1)
+    }
+
+    fn part2(input: &Vec<String>) -> Result<u32> {
+        sum_with(input, extract_v2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::solution::Solution;
+    use crate::tools::lines;
+    use crate::y2023::e1::{extract_v1, extract_v2, sum_on_text, Solver};
+
+    static EXAMPLE_1: &str = include_str!("../../../fixtures/2023/e1/example1.txt");
+    static EXAMPLE_2: &str = include_str!("../../../fixtures/2023/e1/example2.txt");
+
+    #[test]
+    fn extract_simple() {
+        assert_eq!(extract_v1("1abc2".to_string()), Ok(12));
+        assert_eq!(extract_v1("pqr3stu8vwx".to_string()), Ok(38));
+        assert_eq!(extract_v1("a1b2c3d4e5f".to_string()), Ok(15));
+        assert_eq!(extract_v1("treb7uchet".to_string()), Ok(77));
+    }
+    #[test]
+    fn extract_v2_simple() {
+        assert_eq!(extract_v2("two1nine".to_string()), Ok(29));
+        assert_eq!(extract_v2("eightwothree".to_string()), Ok(83));
+        assert_eq!(extract_v2("abcone2threexyz".to_string()), Ok(13));
+        assert_eq!(extract_v2("xtwone3four".to_string()), Ok(24));
+        assert_eq!(extract_v2("4nineeightseven2".to_string()), Ok(42));
+        assert_eq!(extract_v2("zoneight234".to_string()), Ok(14));
+        assert_eq!(extract_v2("7pqrstsixteen".to_string()), Ok(76));
+        assert_eq!(
+            extract_v2("6czklmzsmxgmktzxmxsixmnlfxonetwonesgj".to_string()),
+            Ok(61)
+        );
+    }
+
+    #[test]
+    fn extract_sum() {
+        assert_eq!(sum_on_text(lines(EXAMPLE_1)), Ok(142));
+        assert_eq!(sum_on_text(lines(EXAMPLE_2)), Ok(281));
+    }
+
+    #[test]
+    fn missing_digits() {
+        let input = Solver::parse("1abc2\n\neightwothree\n").unwrap();
+        assert_eq!(Solver::part2(&input), Ok(95));
+        assert_eq!(
+            Solver::part1(&input),
+            Err(
+                Error::parse(1, "eightwothree", "expected a digit, got \"eightwothree\"")
+                    .on_line(3)
+            )
+        );
+    }
+}
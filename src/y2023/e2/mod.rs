@@ -0,0 +1,325 @@
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::tools::lines;
+use regex::Regex;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(PartialEq, Eq, Debug)]
+pub struct RGB {
+    pub r: u32,
+    pub g: u32,
+    pub b: u32,
+}
+
+impl RGB {
+    pub fn possible_in(&self, other: &Self) -> bool {
+        self.r <= other.r && self.g <= other.g && self.b <= other.b
+    }
+
+    /// Product of the counts, unless it does not fit in 32 bits.
+    pub fn power(&self) -> Option<u32> {
+        self.r.checked_mul(self.g)?.checked_mul(self.b)
+    }
+}
+
+pub type Game = (u32, Vec<RGB>);
+
+pub fn game_from_line(source: String) -> Result<Game> {
+    let line = source.as_str();
+    let (game_str, cubes_str) = line.split_once(':').ok_or(Error::parse(
+        line.chars().count() + 1,
+        "",
+        "expected `:` after the game number",
+    ))?;
+
+    let game_regex = Regex::new(r"Game ([0-9]+)").unwrap();
+    let game_number = game_regex
+        .captures(game_str)
+        .and_then(|cap| cap.get(1).unwrap().as_str().parse::<u32>().ok())
+        .ok_or(Error::parse_in(
+            line,
+            game_str,
+            format!("expected `Game <number>`, got \"{}\"", game_str),
+        ))?;
+
+    let mut cube_vec = vec![];
+
+    for pulled in cubes_str.split(';') {
+        let mut new_cube = RGB { r: 0, g: 0, b: 0 };
+        for color in pulled.split(',').map(|color| color.trim()) {
+            let (count_str, name) = color.split_once(' ').ok_or(Error::parse_in(
+                line,
+                color,
+                format!("expected `<count> <color>`, got \"{}\"", color),
+            ))?;
+            let count = count_str.parse::<u32>().map_err(|_| {
+                Error::parse_in(
+                    line,
+                    count_str,
+                    format!("expected a count, got \"{}\"", count_str),
+                )
+            })?;
+            match name.trim() {
+                "blue" => new_cube.b = count,
+                "green" => new_cube.g = count,
+                "red" => new_cube.r = count,
+                other => {
+                    return Err(Error::parse_in(
+                        line,
+                        other,
+                        format!("expected color name, got \"{}\"", other),
+                    ))
+                }
+            };
+        }
+        cube_vec.push(new_cube);
+    }
+    Ok((game_number, cube_vec))
+}
+
+pub fn minimum_rgb((_, cubes): &Game) -> RGB {
+    let mut rgb = RGB { r: 0, g: 0, b: 0 };
+    cubes.iter().for_each(|cub| {
+        rgb.r = u32::max(rgb.r, cub.r);
+        rgb.g = u32::max(rgb.g, cub.g);
+        rgb.b = u32::max(rgb.b, cub.b);
+    });
+    rgb
+}
+
+pub fn games_from_lines(source: &[String]) -> Result<Vec<Game>> {
+    source
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| game_from_line(line.to_owned()).map_err(|err| err.on_line(idx + 1)))
+        .collect()
+}
+
+pub fn possible_sum(games: &[Game], condition: &RGB) -> Result<u32> {
+    games
+        .iter()
+        .filter_map(|(numb, cubes)| {
+            if cubes.iter().all(|c| c.possible_in(condition)) {
+                return Some(numb);
+            }
+            None
+        })
+        .try_fold(0u32, |sum, numb| sum.checked_add(*numb))
+        .ok_or(Error::semantic(
+            "the ids of the possible games add up past 32 bits",
+        ))
+}
+
+pub fn power_sum(games: &[Game]) -> Result<u32> {
+    games
+        .iter()
+        .try_fold(0u32, |sum, game| {
+            sum.checked_add(minimum_rgb(game).power()?)
+        })
+        .ok_or(Error::semantic(
+            "the powers of the games add up past 32 bits",
+        ))
+}
+
+#[cfg(test)]
+fn run_on_text(source: &[String], condition: RGB) -> Result<u32> {
+    possible_sum(&games_from_lines(source)?, &condition)
+}
+
+#[cfg(test)]
+fn run_two_on_text(source: &[String]) -> Result<u32> {
+    power_sum(&games_from_lines(source)?)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Game>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(source: &str) -> Result<Vec<Game>> {
+        games_from_lines(&lines(source))
+    }
+
+    fn part1(input: &Vec<Game>) -> Result<u32> {
+        possible_sum(
+            input,
+            &RGB {
+                r: 12,
+                g: 13,
+                b: 14,
+            },
+        )
+    }
+
+    fn part2(input: &Vec<Game>) -> Result<u32> {
+        power_sum(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::solution::Solution;
+    use crate::tools::lines;
+    use crate::y2023::e2::{
+        game_from_line, minimum_rgb, run_on_text, run_two_on_text, Solver, RGB,
+    };
+
+    #[test]
+    fn interpret_simple() {
+        assert_eq!(
+            game_from_line(
+                "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"
+                    .to_string()
+            ),
+            Ok((
+                3,
+                vec![
+                    RGB { r: 20, g: 8, b: 6 },
+                    RGB { r: 4, g: 13, b: 5 },
+                    RGB { r: 1, g: 5, b: 0 }
+                ]
+            ))
+        )
+    }
+
+    #[test]
+    fn power() {
+        assert_eq!(RGB { r: 4, g: 2, b: 6 }.power(), Some(48));
+        assert_eq!(RGB { r: 1, g: 3, b: 4 }.power(), Some(12));
+        assert_eq!(RGB { r: 20, g: 13, b: 6 }.power(), Some(1560));
+        assert_eq!(RGB { r: 14, g: 3, b: 15 }.power(), Some(630));
+        assert_eq!(RGB { r: 6, g: 3, b: 2 }.power(), Some(36));
+    }
+
+    #[test]
+    fn min_rgb() {
+        assert_eq!(
+            minimum_rgb(
+                &game_from_line(
+                    "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".to_string()
+                )
+                .unwrap()
+            ),
+            RGB { r: 4, g: 2, b: 6 }
+        );
+        assert_eq!(
+            minimum_rgb(
+                &game_from_line(
+                    "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue".to_string()
+                )
+                .unwrap()
+            ),
+            RGB { r: 1, g: 3, b: 4 }
+        );
+        assert_eq!(
+            minimum_rgb(
+                &game_from_line(
+                    "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"
+                        .to_string()
+                )
+                .unwrap()
+            ),
+            RGB { r: 20, g: 13, b: 6 }
+        );
+        assert_eq!(
+            minimum_rgb(
+                &game_from_line(
+                    "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red"
+                        .to_string()
+                )
+                .unwrap()
+            ),
+            RGB { r: 14, g: 3, b: 15 }
+        );
+        assert_eq!(
+            minimum_rgb(
+                &game_from_line(
+                    "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green".to_string()
+                )
+                .unwrap()
+            ),
+            RGB { r: 6, g: 3, b: 2 }
+        );
+    }
+
+    #[test]
+    fn run_text() {
+        let source = include_str!("../../../fixtures/2023/e2/example.txt");
+        assert_eq!(
+            run_on_text(
+                &lines(source),
+                RGB {
+                    r: 12,
+                    g: 13,
+                    b: 14
+                }
+            ),
+            Ok(8)
+        );
+        assert_eq!(run_two_on_text(&lines(source)), Ok(2286))
+    }
+
+    #[test]
+    fn overflows() {
+        assert_eq!(
+            RGB {
+                r: 65536,
+                g: 65536,
+                b: 1
+            }
+            .power(),
+            None
+        );
+        assert_eq!(
+            Solver::part2(&Solver::parse("Game 1: 4294967295 red, 2 green, 1 blue").unwrap()),
+            Err(Error::semantic(
+                "the powers of the games add up past 32 bits"
+            ))
+        );
+        assert_eq!(
+            Solver::part2(
+                &Solver::parse(
+                    "Game 1: 4294967295 red, 1 green, 1 blue\nGame 2: 1 red, 1 green, 1 blue"
+                )
+                .unwrap()
+            ),
+            Err(Error::semantic(
+                "the powers of the games add up past 32 bits"
+            ))
+        );
+        assert_eq!(
+            Solver::part1(&Solver::parse("Game 4294967295: 1 red\nGame 2: 1 blue").unwrap()),
+            Err(Error::semantic(
+                "the ids of the possible games add up past 32 bits"
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Solver::parse("Game 1: 3 blue\n\nGame 3: 1 red, 2 purple").map(|_| ()),
+            Err(Error::parse(18, "purple", "expected color name, got \"purple\"").on_line(3))
+        );
+        assert_eq!(
+            game_from_line("Game 1: x blue".to_string()),
+            Err(Error::parse(9, "x", "expected a count, got \"x\""))
+        );
+        assert_eq!(
+            game_from_line("Game 1 3 blue".to_string()),
+            Err(Error::parse(14, "", "expected `:` after the game number"))
+        );
+        assert_eq!(
+            game_from_line("Gam 1: 3 blue".to_string()),
+            Err(Error::parse(
+                1,
+                "Gam 1",
+                "expected `Game <number>`, got \"Gam 1\""
+            ))
+        );
+    }
+}
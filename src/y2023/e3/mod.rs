@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+use crate::repl::{arg, Command};
+use crate::solution::Solution;
+use crate::tools::lines;
+use crate::trace;
+use regex::Regex;
+
+pub struct World {
+    map: String,
+    width: u32,
+    height: u32,
+}
+
+impl World {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The grid, row after row, without line breaks.
+    pub fn map(&self) -> &str {
+        &self.map
+    }
+
+    pub fn adjacents_chars(&self, index: u32) -> Vec<Adjacency> {
+        adjacents(index, self.width, self.height)
+            .iter()
+            .map(|idx| (*idx, self.map.chars().nth(*idx as usize).unwrap()))
+            .collect()
+    }
+
+    pub fn from_lines(lines: Vec<String>) -> Result<World> {
+        let rows = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| !row.is_empty())
+            .collect::<Vec<_>>();
+        let width = match rows.first() {
+            Some((_, row)) => row.len(),
+            None => return Err(Error::semantic("the grid is empty")),
+        };
+        let numbers = Regex::new(r"[0-9]+").unwrap();
+        for (idx, row) in &rows {
+            if let Some((col, c)) = row.char_indices().find(|(_, c)| !c.is_ascii()) {
+                return Err(Error::parse_in(
+                    row,
+                    &row[col..col + c.len_utf8()],
+                    format!("unexpected character {:?}", c),
+                )
+                .on_line(idx + 1));
+            }
+            if row.len() != width {
+                return Err(Error::parse(
+                    usize::min(row.len(), width) + 1,
+                    row,
+                    format!("expected {} columns, got {}", width, row.len()),
+                )
+                .on_line(idx + 1));
+            }
+            if let Some(number) = numbers
+                .find_iter(row)
+                .find(|number| number.as_str().parse::<u32>().is_err())
+            {
+                return Err(Error::parse_in(
+                    row,
+                    number.as_str(),
+                    format!("number {} does not fit in 32 bits", number.as_str()),
+                )
+                .on_line(idx + 1));
+            }
+        }
+
+        let height = rows.len() as u32;
+        let map = rows.into_iter().map(|(_, row)| row.as_str()).collect();
+        Ok(World {
+            map,
+            width: width as u32,
+            height,
+        })
+    }
+
+    pub fn get_part_numbers(&self) -> (Vec<u32>, HashMap<u32, Vec<u32>>) {
+        let symbols = Regex::new(r"[^\.0-9]").unwrap();
+        let iter = self.map.chars();
+        let mut buffer = "".to_string();
+        let mut adjacency_buffer = vec![];
+        let mut result = vec![];
+        let mut gears = HashMap::<u32, Vec<u32>>::new();
+        iter.enumerate().for_each(|(idx, c)| {
+            if c.is_ascii_digit() {
+                buffer.push(c);
+                adjacency_buffer.extend(self.adjacents_chars(idx as u32));
+            }
+            if (!c.is_ascii_digit() || idx as u32 % self.width == self.width - 1)
+                && !buffer.is_empty()
+            {
+                adjacency_buffer.dedup_by(|a, b| a.0 == b.0);
+                trace::event(|| format!("number {} next to {:?}", buffer, adjacency_buffer));
+
+                if symbols.is_match(
+                    adjacency_buffer
+                        .iter()
+                        .map(|(_, c)| c)
+                        .collect::<String>()
+                        .as_str(),
+                ) {
+                    // `from_lines` checked that every number fits.
+                    let part_number = buffer.parse().unwrap();
+                    result.push(part_number);
+                    adjacency_buffer.iter().for_each(|(idx, c)| {
+                        if *c == '*' {
+                            gears.entry(*idx).or_default().push(part_number);
+                        }
+                    })
+                }
+                adjacency_buffer.clear();
+                buffer.clear();
+            }
+        });
+        gears.retain(|_, nbs| {
+            nbs.dedup();
+            nbs.len() == 2
+        });
+        (result, gears)
+    }
+}
+
+pub type Adjacency = (u32, char);
+
+pub fn adjacents(index: u32, width: u32, height: u32) -> Vec<u32> {
+    let iwidth = width as i32;
+    let iheight = height as i32;
+    let iindex = index as i32;
+
+    let mut adjacency = vec![];
+
+    match iindex % iwidth {
+        0 => {
+            // first column
+            adjacency.extend_from_slice(&[
+                iindex - iwidth,
+                iindex - iwidth + 1,
+                iindex + 1,
+                iindex + iwidth,
+                iindex + iwidth + 1,
+            ])
+        }
+        i if i == iwidth - 1 => {
+            // last column
+            adjacency.extend_from_slice(&[
+                iindex - iwidth,
+                iindex - iwidth - 1,
+                iindex - 1,
+                iindex + iwidth,
+                iindex + iwidth - 1,
+            ])
+        }
+        _ => {
+            // last column
+            adjacency.extend_from_slice(&[
+                iindex - iwidth - 1,
+                iindex - iwidth,
+                iindex - iwidth + 1,
+                iindex - 1,
+                iindex + 1,
+                iindex + iwidth - 1,
+                iindex + iwidth,
+                iindex + iwidth + 1,
+            ])
+        }
+    }
+    adjacency
+        .iter()
+        .filter_map(|f| {
+            if *f >= 0 && *f < iwidth * iheight {
+                Some(*f as u32)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Shows the cell at a line and column of the grid, both from 1, within its neighborhood.
+fn show_cell(input: &World, args: &[&str]) -> Result<String> {
+    let (line, column): (u32, u32) = (arg(args, 0, "line")?, arg(args, 1, "column")?);
+    if !(1..=input.height).contains(&line) || !(1..=input.width).contains(&column) {
+        return Err(Error::semantic(format!(
+            "no cell at line {}, column {} of the {}x{} grid",
+            line, column, input.width, input.height
+        )));
+    }
+    let index = (line - 1) * input.width + column - 1;
+    let cell = input.map.as_bytes()[index as usize] as char;
+    let mut rows = vec![format!("{:?} at line {}, column {}:", cell, line, column)];
+    for row in line.saturating_sub(1).max(1)..=(line + 1).min(input.height) {
+        let start = (row - 1) * input.width;
+        let (from, to) = (column.saturating_sub(2), (column + 1).min(input.width));
+        rows.push(format!(
+            "  {}",
+            &input.map[(start + from) as usize..(start + to) as usize]
+        ));
+    }
+    Ok(rows.join("\n"))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = World;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(source: &str) -> Result<World> {
+        World::from_lines(lines(source))
+    }
+
+    fn part1(input: &World) -> Result<u32> {
+        input
+            .get_part_numbers()
+            .0
+            .iter()
+            .try_fold(0u32, |sum, number| sum.checked_add(*number))
+            .ok_or(Error::semantic("the part numbers add up past 32 bits"))
+    }
+
+    fn part2(input: &World) -> Result<u32> {
+        input
+            .get_part_numbers()
+            .1
+            .values()
+            .try_fold(0u32, |sum, gear| {
+                sum.checked_add(gear[0].checked_mul(gear[1])?)
+            })
+            .ok_or(Error::semantic("the gear ratios add up past 32 bits"))
+    }
+
+    fn commands() -> Vec<Command<World>> {
+        vec![Command {
+            name: "cell",
+            help: "<line> <column>: a cell of the grid with its neighbors",
+            run: show_cell,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::y2023::e3::adjacents;
+
+    use crate::solution::Solution;
+
+    use super::{show_cell, Solver, World};
+
+    #[test]
+    fn adjacent_simple() {
+        // 01234
+        // 56789
+        // 01234
+        // 56789
+        assert_eq!(adjacents(0, 5, 4), vec![1, 5, 6]);
+        assert_eq!(adjacents(5, 5, 4), vec![0, 1, 6, 10, 11]);
+        assert_eq!(adjacents(2, 5, 4), vec![1, 3, 6, 7, 8]);
+        assert_eq!(adjacents(7, 5, 4), vec![1, 2, 3, 6, 8, 11, 12, 13]);
+        assert_eq!(adjacents(19, 5, 4), vec![14, 13, 18]);
+    }
+
+    #[test]
+    fn adjacents_chars_simple() {
+        let w = World {
+            map: "01234567890123456789".to_string(),
+            width: 5,
+            height: 4,
+        };
+        assert_eq!(w.adjacents_chars(0), vec![(1, '1'), (5, '5'), (6, '6')]);
+        assert_eq!(
+            w.adjacents_chars(14),
+            vec![(9, '9'), (8, '8'), (13, '3'), (19, '9'), (18, '8')]
+        );
+    }
+
+    #[test]
+    fn cell_command() {
+        let w = World {
+            map: "01234567890123456789".to_string(),
+            width: 5,
+            height: 4,
+        };
+        assert_eq!(
+            show_cell(&w, &["2", "3"]),
+            Ok("'7' at line 2, column 3:\n  123\n  678\n  123".to_string())
+        );
+        assert_eq!(
+            show_cell(&w, &["4", "5"]),
+            Ok("'9' at line 4, column 5:\n  34\n  89".to_string())
+        );
+        assert_eq!(
+            show_cell(&w, &["5", "1"]),
+            Err(Error::semantic(
+                "no cell at line 5, column 1 of the 5x4 grid"
+            ))
+        );
+    }
+
+    #[test]
+    fn build_world() {
+        let w = World::from_lines(vec![
+            "01234".to_string(),
+            "56789".to_string(),
+            "01234".to_string(),
+            "56789".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(w.map, "01234567890123456789".to_string());
+        assert_eq!(w.height, 4);
+        assert_eq!(w.width, 5);
+
+        assert_eq!(
+            World::from_lines(vec![
+                "..7...........".to_string(),
+                "1.99999999999*".to_string()
+            ])
+            .map(|_| ()),
+            Err(Error::parse(
+                3,
+                "99999999999",
+                "number 99999999999 does not fit in 32 bits"
+            )
+            .on_line(2))
+        );
+        let big = World::from_lines(vec!["4294967295*4294967294".to_string()]).unwrap();
+        assert_eq!(
+            Solver::part1(&big),
+            Err(Error::semantic("the part numbers add up past 32 bits"))
+        );
+        assert_eq!(
+            Solver::part2(&big),
+            Err(Error::semantic("the gear ratios add up past 32 bits"))
+        );
+    }
+
+    #[test]
+    fn parse_numbers() {
+        let w0 = World {
+            map: ".212.5..9812345.....".to_string(),
+            width: 5,
+            height: 4,
+        };
+        assert_eq!(w0.get_part_numbers().0, vec![]);
+        let w1 = World {
+            map: "*212.5//9812345*....".to_string(),
+            width: 5,
+            height: 4,
+        };
+        assert_eq!(w1.get_part_numbers().0, vec![212, 5, 98, 12345]);
+        let w2 = World {
+            map: "*212.5..9812345.....".to_string(),
+            width: 5,
+            height: 4,
+        };
+        assert_eq!(w2.get_part_numbers().0, vec![212, 5]);
+        let w3 = World {
+            map: "467..114.....*........35..633.......#...617*...........+.58...592...........755....$.*.....664.598..".to_string(),
+            height: 10,
+            width: 10,
+        };
+        assert_eq!(
+            w3.get_part_numbers().0,
+            vec![467, 35, 633, 617, 592, 755, 664, 598]
+        );
+        assert_eq!(w3.get_part_numbers().1.get(&13).unwrap(), &vec![467, 35]);
+        assert_eq!(w3.get_part_numbers().1.get(&85).unwrap(), &vec![755, 598]);
+        assert!(!w3.get_part_numbers().1.contains_key(&43));
+    }
+
+    #[test]
+    fn build_errors() {
+        assert_eq!(
+            World::from_lines(vec!["01234".to_string(), "567".to_string()]).map(|_| ()),
+            Err(Error::parse(4, "567", "expected 5 columns, got 3").on_line(2))
+        );
+        assert_eq!(
+            World::from_lines(vec!["".to_string(), "..é.".to_string()]).map(|_| ()),
+            Err(Error::parse(3, "é", "unexpected character 'é'").on_line(2))
+        );
+        assert_eq!(
+            World::from_lines(vec![]).map(|_| ()),
+            Err(Error::semantic("the grid is empty"))
+        );
+    }
+}
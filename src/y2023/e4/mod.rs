@@ -0,0 +1,297 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{Error, Result};
+use crate::repl::{arg, Command};
+use crate::solution::Solution;
+use crate::tools::lines;
+use regex::Regex;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Card {
+    id: u32,
+    winnings: HashSet<u32>,
+    numbers: HashSet<u32>,
+    win_count: u32,
+    win_score: u32,
+    exp_cards: Vec<u32>,
+}
+
+impl Card {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn winnings(&self) -> &HashSet<u32> {
+        &self.winnings
+    }
+
+    pub fn numbers(&self) -> &HashSet<u32> {
+        &self.numbers
+    }
+
+    pub fn win_count(&self) -> u32 {
+        self.win_count
+    }
+
+    pub fn win_score(&self) -> u32 {
+        self.win_score
+    }
+
+    /// Ids of the cards won by this one.
+    pub fn exp_cards(&self) -> &[u32] {
+        &self.exp_cards
+    }
+}
+
+pub type CardSet = HashMap<u32, Card>;
+
+fn parse_numbers(line: &str, numbers: &str) -> Result<HashSet<u32>> {
+    numbers
+        .split_whitespace()
+        .map(|x| {
+            x.parse::<u32>()
+                .map_err(|_| Error::parse_in(line, x, format!("expected a number, got \"{}\"", x)))
+        })
+        .collect()
+}
+
+pub fn parse_card(source: String) -> Result<Card> {
+    let card_regex = Regex::new(r"Card[ ]*(?<i>[0-9]+): (?<w>[\ 0-9]+)\|(?<m>[\ 0-9]+)").unwrap();
+    let line = source.as_str();
+    let cap = card_regex.captures(line).ok_or(Error::parse(
+        1,
+        line,
+        "expected `Card <id>: <winning numbers> | <numbers>`",
+    ))?;
+    let id_str = cap.name("i").unwrap().as_str();
+    let id = id_str
+        .parse::<u32>()
+        .map_err(|_| Error::parse_in(line, id_str, format!("card id {} is too large", id_str)))?;
+    let winnings = parse_numbers(line, cap.name("w").unwrap().as_str())?;
+    let numbers = parse_numbers(line, cap.name("m").unwrap().as_str())?;
+    let win_count = numbers.intersection(&winnings).count() as u32;
+    let win_score = match win_count {
+        0 => 0,
+        _ => 2u32.checked_pow(win_count - 1).ok_or(Error::parse(
+            1,
+            line,
+            format!("{} winning numbers score past 32 bits", win_count),
+        ))?,
+    };
+    if id.checked_add(win_count).is_none() {
+        return Err(Error::parse_in(
+            line,
+            id_str,
+            format!(
+                "card {} wins a copy of card {}, which does not exist",
+                id,
+                id as u64 + win_count as u64
+            ),
+        ));
+    }
+    let exp_cards = (1..=win_count).map(|i| id + i).collect();
+    Ok(Card {
+        id,
+        winnings,
+        numbers,
+        win_count,
+        win_score,
+        exp_cards,
+    })
+}
+
+/// Parses every card, checking that ids are unique and that the copies won exist.
+pub fn prepare_card_set(source: &[String]) -> Result<CardSet> {
+    let mut card_set = CardSet::new();
+    for (idx, l) in source.iter().enumerate() {
+        if l.is_empty() {
+            continue;
+        }
+        let c = parse_card(l.to_string()).map_err(|err| err.on_line(idx + 1))?;
+        if card_set.contains_key(&c.id) {
+            return Err(
+                Error::parse(1, l, format!("card {} is listed twice", c.id)).on_line(idx + 1)
+            );
+        }
+        card_set.insert(c.id, c);
+    }
+    for card in card_set.values() {
+        if let Some(missing) = card.exp_cards.iter().find(|id| !card_set.contains_key(id)) {
+            return Err(Error::semantic(format!(
+                "card {} wins a copy of card {}, which does not exist",
+                card.id, missing
+            )));
+        }
+    }
+    Ok(card_set)
+}
+
+fn new_cards<'a>(source: &'a CardSet, cards: &[&Card]) -> Vec<&'a Card> {
+    let mut new_cards_list = vec![];
+    for card in cards {
+        let cnt = card.win_count;
+        for i in 1..=cnt {
+            new_cards_list.push(source.get(&(card.id + i)).unwrap());
+        }
+    }
+    new_cards_list
+}
+
+pub fn expand(source: &CardSet) -> Vec<&Card> {
+    let mut expanded = vec![];
+    source.values().for_each(|card| {
+        expanded.push(card);
+    });
+    let mut added = new_cards(source, &expanded);
+    while !added.is_empty() {
+        expanded.append(&mut added.clone());
+        added = new_cards(source, &added);
+    }
+
+    expanded
+}
+
+/// Describes the winning numbers of a card and the copies it wins.
+fn describe_card(input: &CardSet, args: &[&str]) -> Result<String> {
+    let id = arg(args, 0, "card id")?;
+    let card = input
+        .get(&id)
+        .ok_or(Error::semantic(format!("no card {}", id)))?;
+    Ok(match card.win_count {
+        0 => format!("card {}: no winning number", id),
+        count => format!(
+            "card {}: {} winning numbers, worth {} points, wins a copy of cards {}",
+            id,
+            count,
+            card.win_score,
+            card.exp_cards
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    })
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = CardSet;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse(source: &str) -> Result<CardSet> {
+        prepare_card_set(&lines(source))
+    }
+
+    fn part1(input: &CardSet) -> Result<u32> {
+        input
+            .values()
+            .try_fold(0u32, |sum, card| sum.checked_add(card.win_score))
+            .ok_or(Error::semantic("the scores add up past 32 bits"))
+    }
+
+    fn part2(input: &CardSet) -> Result<usize> {
+        Ok(expand(input).len())
+    }
+
+    fn commands() -> Vec<Command<CardSet>> {
+        vec![Command {
+            name: "card",
+            help: "<id>: winning numbers of a card and copies it wins",
+            run: describe_card,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::error::Error;
+    use crate::tools::lines;
+    use crate::y2023::e4::{expand, parse_card, prepare_card_set, Card};
+
+    #[test]
+    fn test_expand() {
+        let cs = prepare_card_set(&lines(include_str!(
+            "../../../fixtures/2023/e4/example.txt"
+        )))
+        .unwrap();
+        assert_eq!(expand(&cs).len(), 30);
+    }
+
+    #[test]
+    fn test_parse_card() {
+        assert_eq!(
+            parse_card("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".to_string()),
+            Ok(Card {
+                id: 1,
+                winnings: HashSet::from([41, 48, 83, 86, 17]),
+                numbers: HashSet::from([83, 86, 6, 31, 17, 9, 48, 53]),
+                win_count: 4,
+                win_score: 8,
+                exp_cards: vec![2, 3, 4, 5]
+            })
+        )
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            parse_card("Card 1: 41 48 | 83".to_string()).map(|_| ()),
+            Ok(())
+        );
+        assert_eq!(
+            parse_card("Card 1: 41 48 83".to_string()),
+            Err(Error::parse(
+                1,
+                "Card 1: 41 48 83",
+                "expected `Card <id>: <winning numbers> | <numbers>`"
+            ))
+        );
+        assert_eq!(
+            parse_card("Card 1: 41 99999999999 | 83".to_string()),
+            Err(Error::parse(
+                12,
+                "99999999999",
+                "expected a number, got \"99999999999\""
+            ))
+        );
+        assert_eq!(
+            prepare_card_set(&["Card 1: 41 | 41".to_string()]),
+            Err(Error::semantic(
+                "card 1 wins a copy of card 2, which does not exist"
+            ))
+        );
+        assert_eq!(
+            prepare_card_set(&[
+                "Card 1: 41 | 40".to_string(),
+                String::new(),
+                "Card 1: 41 | 40".to_string()
+            ]),
+            Err(Error::parse(1, "Card 1: 41 | 40", "card 1 is listed twice").on_line(3))
+        );
+        assert_eq!(
+            parse_card("Card 4294967295: 1 | 1".to_string()),
+            Err(Error::parse(
+                6,
+                "4294967295",
+                "card 4294967295 wins a copy of card 4294967296, which does not exist"
+            ))
+        );
+        let many = (1..=33)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let line = format!("Card 1: {} | {}", many, many);
+        assert_eq!(
+            parse_card(line.clone()),
+            Err(Error::parse(
+                1,
+                &line,
+                "33 winning numbers score past 32 bits"
+            ))
+        );
+    }
+}
@@ -0,0 +1,1232 @@
+use std::fmt::{self, Display};
+
+use regex::Regex;
+
+use crate::error::{Error, Result};
+use crate::repl::{arg, Command};
+use crate::solution::Solution;
+use crate::tools::lines;
+use crate::trace;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct MappingElement {
+    pub source_start: u64,
+    pub destination_start: u64,
+    pub range_length: u64,
+}
+
+impl MappingElement {
+    pub fn from_line(source: String) -> Result<MappingElement> {
+        let line = source.as_str();
+        let numbers = parse_numbers(line, line)?;
+        match numbers[..] {
+            [destination_start, source_start, range_length] => Ok(MappingElement {
+                destination_start,
+                source_start,
+                range_length,
+            }),
+            _ => Err(Error::parse(
+                1,
+                line,
+                format!(
+                    "expected `<destination> <source> <length>`, got {} numbers",
+                    numbers.len()
+                ),
+            )),
+        }
+    }
+
+    /// Source range as `<start>..<end>`, once checked not to overflow.
+    fn source_range(&self) -> String {
+        format!(
+            "{}..{}",
+            self.source_start,
+            self.source_start + self.range_length
+        )
+    }
+}
+
+/// Written as in the almanac, `<destination> <source> <length>`.
+impl Display for MappingElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.destination_start, self.source_start, self.range_length
+        )
+    }
+}
+
+/// Parses the whitespace separated numbers of `numbers`, a slice of `line`.
+fn parse_numbers(line: &str, numbers: &str) -> Result<Vec<u64>> {
+    numbers
+        .split_whitespace()
+        .map(|x| {
+            x.parse::<u64>()
+                .map_err(|_| Error::parse_in(line, x, format!("expected a number, got \"{}\"", x)))
+        })
+        .collect()
+}
+
+pub fn traverse_mappings(start: u64, mappings: &[Mapping]) -> u64 {
+    mappings.iter().fold(start, |acc, e| e.please_map(acc))
+}
+
+/// Follows the values of `[start, end)` through every mapping, returning the ranges they end up in.
+pub fn traverse_ranges(start: u64, end: u64, mappings: &[Mapping]) -> Vec<(u64, u64)> {
+    mappings.iter().fold(vec![(start, end)], |ranges, mapping| {
+        ranges
+            .into_iter()
+            .flat_map(|(start, end)| mapping.map_range(start, end))
+            .collect()
+    })
+}
+
+/// Values leading to `[start, end)` through every mapping, as sorted, disjoint ranges.
+pub fn traverse_preimages(start: u64, end: u64, mappings: &[Mapping]) -> Vec<(u64, u64)> {
+    mappings
+        .iter()
+        .rev()
+        .fold(vec![(start, end)], |ranges, mapping| {
+            merge_ranges(
+                ranges
+                    .into_iter()
+                    .flat_map(|(start, end)| mapping.preimage(start, end))
+                    .collect(),
+            )
+        })
+}
+
+/// Lowest location reached by a value of `seeds`, given as `[start, end)` ranges, found by
+/// bisecting on the locations whose preimage meets them.
+pub fn lowest_location(seeds: &[(u64, u64)], mappings: &[Mapping]) -> Option<u64> {
+    let location = collapse_mappings(mappings);
+    let reached = |highest: u64| {
+        location
+            .preimage(0, highest + 1)
+            .iter()
+            .any(|&(start, end)| {
+                seeds
+                    .iter()
+                    .any(|&(seed_start, seed_end)| start.max(seed_start) < end.min(seed_end))
+            })
+    };
+    let (mut low, mut high) = (0, u64::MAX - 1);
+    if !reached(high) {
+        return None;
+    }
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if reached(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    Some(low)
+}
+
+/// Sorts `ranges`, joining those that overlap or touch.
+fn merge_ranges(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    ranges.sort();
+    let mut merged: Vec<(u64, u64)> = vec![];
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Why elements cannot make a mapping, given by their positions.
+enum Invalid {
+    /// The element at `idx` cannot be mapped, because of its `number`-th number.
+    Element {
+        idx: usize,
+        number: usize,
+        problem: &'static str,
+    },
+    /// The source range of the element at `second` overlaps that of the earlier one at `first`.
+    Overlap { first: usize, second: usize },
+}
+
+/// Checks that every element of `defs` is a non-empty range within `u64`, then that no two source
+/// ranges overlap.
+fn check(defs: &[MappingElement]) -> std::result::Result<(), Invalid> {
+    for (idx, def) in defs.iter().enumerate() {
+        let (number, problem) = if def.range_length == 0 {
+            (2, "empty range")
+        } else if def.source_start.checked_add(def.range_length).is_none() {
+            (1, "source range ends past the largest value")
+        } else if def
+            .destination_start
+            .checked_add(def.range_length)
+            .is_none()
+        {
+            (0, "destination range ends past the largest value")
+        } else {
+            continue;
+        };
+        return Err(Invalid::Element {
+            idx,
+            number,
+            problem,
+        });
+    }
+    let mut order = (0..defs.len()).collect::<Vec<usize>>();
+    order.sort_by_key(|&idx| defs[idx].source_start);
+    for pair in order.windows(2) {
+        let (before, after) = (&defs[pair[0]], &defs[pair[1]]);
+        if before.source_start + before.range_length > after.source_start {
+            return Err(Invalid::Overlap {
+                first: pair[0].min(pair[1]),
+                second: pair[0].max(pair[1]),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub struct Mapping {
+    defs: Vec<MappingElement>,
+}
+
+impl Mapping {
+    /// Mapping made of `defs`, checking that each is a range of values that can be mapped and
+    /// that their source ranges do not overlap.
+    pub fn new(defs: Vec<MappingElement>) -> Result<Mapping> {
+        match check(&defs) {
+            Ok(()) => {
+                let mut mapping = Mapping { defs };
+                mapping.sort();
+                Ok(mapping)
+            }
+            Err(Invalid::Element { idx, problem, .. }) => {
+                Err(Error::semantic(format!("{} in `{}`", problem, defs[idx])))
+            }
+            Err(Invalid::Overlap { first, second }) => Err(Error::semantic(format!(
+                "source range {} of `{}` overlaps {} of `{}`",
+                defs[second].source_range(),
+                defs[second],
+                defs[first].source_range(),
+                defs[first]
+            ))),
+        }
+    }
+
+    pub fn defs(&self) -> &[MappingElement] {
+        &self.defs
+    }
+
+    pub fn sort(&mut self) {
+        self.defs
+            .sort_by(|a, b| a.source_start.partial_cmp(&b.source_start).unwrap());
+    }
+    /// Value `idx` is mapped to, found with a single lookup in the sorted elements.
+    pub fn please_map(&self, idx: u64) -> u64 {
+        let after = self.defs.partition_point(|def| def.source_start <= idx);
+        match after.checked_sub(1).map(|last| &self.defs[last]) {
+            Some(def) if idx - def.source_start < def.range_length => {
+                def.destination_start + (idx - def.source_start)
+            }
+            _ => idx,
+        }
+    }
+
+    /// Splits `[start, end)` into the pieces handled by the same element, or by none, returned as
+    /// `(source start, source end, destination start)` in no particular order.
+    fn split(&self, start: u64, end: u64) -> Vec<(u64, u64, u64)> {
+        let mut mapped = vec![];
+        let mut pending = vec![(start, end)];
+        for def in &self.defs {
+            let (def_start, def_end) = (def.source_start, def.source_start + def.range_length);
+            let mut rest = vec![];
+            for (start, end) in pending {
+                let (from, to) = (start.max(def_start), end.min(def_end));
+                if from >= to {
+                    rest.push((start, end));
+                    continue;
+                }
+                mapped.push((from, to, def.destination_start + (from - def_start)));
+                if start < from {
+                    rest.push((start, from));
+                }
+                if to < end {
+                    rest.push((to, end));
+                }
+            }
+            pending = rest;
+        }
+        mapped.extend(
+            pending
+                .into_iter()
+                .filter(|(start, end)| start < end)
+                .map(|(start, end)| (start, end, start)),
+        );
+        mapped
+    }
+
+    /// Maps the values of `[start, end)`, returning the ranges they land in, in no particular
+    /// order. Values outside of every element keep their value, as in `please_map`.
+    pub fn map_range(&self, start: u64, end: u64) -> Vec<(u64, u64)> {
+        self.split(start, end)
+            .into_iter()
+            .map(|(start, end, destination)| (destination, destination + (end - start)))
+            .collect()
+    }
+
+    /// Values mapped into `[start, end)`, as sorted, disjoint ranges: those of the elements reaching
+    /// it along with the part of it no element covers, which keeps its value.
+    pub fn preimage(&self, start: u64, end: u64) -> Vec<(u64, u64)> {
+        let mut sources = vec![];
+        for (source_start, source_end, destination) in self.split(0, u64::MAX) {
+            let destination_end = destination + (source_end - source_start);
+            let (from, to) = (start.max(destination), end.min(destination_end));
+            if from < to {
+                sources.push((
+                    source_start + (from - destination),
+                    source_start + (to - destination),
+                ));
+            }
+        }
+        merge_ranges(sources)
+    }
+
+    /// Mapping of `self` followed by `next`, with sorted, disjoint elements, leaving out the values
+    /// that both keep.
+    pub fn compose(&self, next: &Mapping) -> Mapping {
+        let mut defs = vec![];
+        for (start, end, destination) in self.split(0, u64::MAX) {
+            for (from, to, target) in next.split(destination, destination + (end - start)) {
+                let source_start = start + (from - destination);
+                if source_start != target {
+                    defs.push(MappingElement {
+                        source_start,
+                        destination_start: target,
+                        range_length: to - from,
+                    });
+                }
+            }
+        }
+        defs.sort_by_key(|def| def.source_start);
+
+        // Join the pieces that an element of `next` cut but that move by the same offset.
+        let mut merged: Vec<MappingElement> = vec![];
+        for def in defs {
+            match merged.last_mut() {
+                Some(last)
+                    if last.source_start + last.range_length == def.source_start
+                        && last.destination_start + last.range_length == def.destination_start =>
+                {
+                    last.range_length += def.range_length
+                }
+                _ => merged.push(def),
+            }
+        }
+        Mapping { defs: merged }
+    }
+}
+
+/// Single mapping equivalent to following every mapping in turn.
+pub fn collapse_mappings(mappings: &[Mapping]) -> Mapping {
+    mappings
+        .iter()
+        .fold(Mapping { defs: vec![] }, |collapsed, mapping| {
+            collapsed.compose(mapping)
+        })
+}
+
+/// Map of an almanac as written, before its place in the chain of categories is known.
+struct Section {
+    source: String,
+    destination: String,
+    header: String,
+    line: usize,
+    /// Elements along with the line they are on and its text.
+    defs: Vec<(usize, String, MappingElement)>,
+}
+
+impl Section {
+    /// Checks the elements as `Mapping::new` does, locating problems on their line.
+    fn check(&self) -> Result<()> {
+        let defs = self.defs.iter().map(|(_, _, def)| *def).collect::<Vec<_>>();
+        match check(&defs) {
+            Ok(()) => Ok(()),
+            Err(Invalid::Element {
+                idx,
+                number,
+                problem,
+            }) => {
+                let (line, text, _) = &self.defs[idx];
+                let part = text.split_whitespace().nth(number).unwrap();
+                Err(
+                    Error::parse_in(text, part, format!("{} in `{}`", problem, self.header))
+                        .on_line(*line),
+                )
+            }
+            Err(Invalid::Overlap { first, second }) => {
+                let (first, second) = (&self.defs[first], &self.defs[second]);
+                Err(Error::parse(
+                    1,
+                    &second.1,
+                    format!(
+                        "source range {} overlaps {} on line {} in `{}`",
+                        second.2.source_range(),
+                        first.2.source_range(),
+                        first.0,
+                        self.header
+                    ),
+                )
+                .on_line(second.0))
+            }
+        }
+    }
+}
+
+pub fn parse(source: Vec<String>) -> Result<ParseResult> {
+    let header = Regex::new(r"^(\w+)-to-(\w+) map:$").unwrap();
+    let mut seeds = vec![];
+    let mut sections: Vec<Section> = vec![];
+
+    for (idx, line) in source.into_iter().enumerate() {
+        if let Some(seed_str) = line.strip_prefix("seeds: ") {
+            seeds = parse_numbers(&line, seed_str).map_err(|err| err.on_line(idx + 1))?;
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(captures) = header.captures(&line) {
+            sections.push(Section {
+                source: captures[1].to_string(),
+                destination: captures[2].to_string(),
+                header: line.clone(),
+                line: idx + 1,
+                defs: vec![],
+            });
+            continue;
+        }
+        let Some(section) = sections.last_mut() else {
+            return Err(Error::parse(
+                1,
+                &line,
+                "expected a `<source>-to-<destination> map:` header",
+            )
+            .on_line(idx + 1));
+        };
+        let def = MappingElement::from_line(line.clone()).map_err(|err| err.on_line(idx + 1))?;
+        section.defs.push((idx + 1, line, def));
+    }
+
+    for section in &sections {
+        section.check()?;
+    }
+    if seeds.is_empty() {
+        return Err(Error::semantic("no seeds listed in the almanac"));
+    }
+    let (categories, mappings) = chain(sections)?;
+    Ok(ParseResult {
+        seeds,
+        categories,
+        mappings,
+    })
+}
+
+/// Orders `sections` along the chain of categories they form, starting from the only category
+/// that no map leads to.
+fn chain(sections: Vec<Section>) -> Result<(Vec<String>, Vec<Mapping>)> {
+    for (idx, section) in sections.iter().enumerate() {
+        for earlier in &sections[..idx] {
+            // The header reads `<source>-to-<destination> map:`.
+            let (start, repeated, relation) = if earlier.source == section.source {
+                (0, &section.source, "mapped")
+            } else if earlier.destination == section.destination {
+                (section.source.len() + 4, &section.destination, "mapped to")
+            } else {
+                continue;
+            };
+            return Err(Error::parse_in(
+                &section.header,
+                &section.header[start..start + repeated.len()],
+                format!(
+                    "`{}` is already {} by `{}` on line {}",
+                    repeated, relation, earlier.header, earlier.line
+                ),
+            )
+            .on_line(section.line));
+        }
+    }
+
+    let starts = sections
+        .iter()
+        .map(|section| &section.source)
+        .filter(|&source| sections.iter().all(|s| &s.destination != source))
+        .collect::<Vec<&String>>();
+    let start = match starts[..] {
+        [] if sections.is_empty() => return Ok((vec![], vec![])),
+        [] => {
+            return Err(Error::semantic(
+                "the maps form a cycle, with no category to start from",
+            ))
+        }
+        [start] => start.clone(),
+        _ => {
+            let starts = starts
+                .iter()
+                .map(|start| format!("`{}`", start))
+                .collect::<Vec<String>>();
+            return Err(Error::semantic(format!(
+                "the maps form separate chains, starting from {}",
+                starts.join(" and ")
+            )));
+        }
+    };
+
+    let mut categories = vec![start];
+    let mut mappings = vec![];
+    let mut remaining = sections;
+    while let Some(idx) = remaining
+        .iter()
+        .position(|section| Some(&section.source) == categories.last())
+    {
+        let section = remaining.remove(idx);
+        categories.push(section.destination);
+        mappings.push(Mapping::new(
+            section.defs.into_iter().map(|(_, _, def)| def).collect(),
+        )?);
+    }
+    match remaining.first() {
+        Some(section) => Err(Error::semantic(format!(
+            "`{}` on line {} is not on the chain from `{}`",
+            section.header, section.line, categories[0]
+        ))),
+        None => Ok((categories, mappings)),
+    }
+}
+
+pub struct ParseResult {
+    pub seeds: Vec<u64>,
+    /// Categories in the order of the chain, `mappings[i]` leading from `categories[i]` to the
+    /// next one.
+    pub categories: Vec<String>,
+    pub mappings: Vec<Mapping>,
+}
+
+impl ParseResult {
+    /// Positions in `categories` of `from` and `to`, when the maps lead from one to the other.
+    fn path(&self, from: &str, to: &str) -> Result<(usize, usize)> {
+        let position = |name: &str| {
+            self.categories
+                .iter()
+                .position(|category| category == name)
+                .ok_or_else(|| Error::semantic(format!("no `{}` category in the almanac", name)))
+        };
+        let (start, end) = (position(from)?, position(to)?);
+        if start > end {
+            return Err(Error::semantic(format!(
+                "no path from `{}` to `{}`, the maps lead from `{}` to `{}`",
+                from,
+                to,
+                self.categories[0],
+                self.categories.last().unwrap()
+            )));
+        }
+        Ok((start, end))
+    }
+
+    /// Mappings leading from the category `from` to `to`, such as `soil` to `humidity`.
+    pub fn between(&self, from: &str, to: &str) -> Result<&[Mapping]> {
+        let (start, end) = self.path(from, to)?;
+        Ok(&self.mappings[start..end])
+    }
+}
+
+/// Follows a seed through every mapping of the almanac.
+fn trace_seed(input: &ParseResult, args: &[&str]) -> Result<String> {
+    let mut value: u64 = arg(args, 0, "seed")?;
+    let mut steps = vec![value.to_string()];
+    for mapping in input.between("seed", "location")? {
+        value = mapping.please_map(value);
+        steps.push(value.to_string());
+    }
+    Ok(steps.join(" -> "))
+}
+
+/// Follows a value of one category through the maps leading to another.
+fn convert(input: &ParseResult, args: &[&str]) -> Result<String> {
+    let from: String = arg(args, 0, "source category")?;
+    let to: String = arg(args, 1, "destination category")?;
+    let mut value: u64 = arg(args, 2, "value")?;
+    let (start, end) = input.path(&from, &to)?;
+    let mut steps = vec![format!("{} {}", from, value)];
+    for (mapping, category) in input.mappings[start..end]
+        .iter()
+        .zip(&input.categories[start + 1..])
+    {
+        value = mapping.please_map(value);
+        steps.push(format!("{} {}", category, value));
+    }
+    Ok(steps.join(" -> "))
+}
+
+/// Lists the seeds ending up at a location, whether listed in the almanac or not.
+fn find_seeds(input: &ParseResult, args: &[&str]) -> Result<String> {
+    let location: u64 = arg(args, 0, "location")?;
+    let mappings = input.between("seed", "location")?;
+    let seeds = traverse_preimages(location, location.saturating_add(1), mappings)
+        .into_iter()
+        .flat_map(|(start, end)| start..end)
+        .map(|seed| {
+            let listed = input
+                .seeds
+                .chunks(2)
+                .any(|pair| pair.len() == 2 && pair[0] <= seed && seed - pair[0] < pair[1]);
+            format!("{}{}", seed, if listed { " (listed)" } else { "" })
+        })
+        .collect::<Vec<String>>();
+    match seeds.is_empty() {
+        true => Ok(format!("no seed ends up at {}", location)),
+        false => Ok(format!("{} <- {}", location, seeds.join(", "))),
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = ParseResult;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(source: &str) -> Result<ParseResult> {
+        parse(lines(source))
+    }
+
+    fn part1(input: &ParseResult) -> Result<u64> {
+        let location = {
+            let _span = trace::span("collapse");
+            collapse_mappings(input.between("seed", "location")?)
+        };
+        Ok(input
+            .seeds
+            .iter()
+            .map(|seed| location.please_map(*seed))
+            .min()
+            .unwrap())
+    }
+
+    fn part2(input: &ParseResult) -> Result<u64> {
+        if !input.seeds.len().is_multiple_of(2) {
+            return Err(Error::semantic(
+                "seeds must come in `<start> <length>` pairs",
+            ));
+        }
+        let mappings = input.between("seed", "location")?;
+        let mut result = None;
+        for pair in input.seeds.chunks(2) {
+            let (seed_start, seed_length) = (pair[0], pair[1]);
+            let _span = trace::span_with("range", || {
+                format!("{} seeds from {}", seed_length, seed_start)
+            });
+            let seed_end = seed_start
+                .checked_add(seed_length)
+                .ok_or(Error::semantic(format!(
+                    "seed range `{} {}` ends past the largest value",
+                    seed_start, seed_length
+                )))?;
+            let locations = traverse_ranges(seed_start, seed_end, mappings);
+            trace::event(|| format!("{} location ranges", locations.len()));
+            let lowest = locations.iter().map(|(start, _)| *start).min();
+            result = result.into_iter().chain(lowest).min();
+        }
+        result.ok_or(Error::semantic("every seed range is empty"))
+    }
+
+    fn commands() -> Vec<Command<ParseResult>> {
+        vec![
+            Command {
+                name: "seed",
+                help: "<seed>: values of a seed through each mapping, down to its location",
+                run: trace_seed,
+            },
+            Command {
+                name: "location",
+                help: "<location>: seeds ending up at a location, marking those in the seed ranges",
+                run: find_seeds,
+            },
+            Command {
+                name: "convert",
+                help: "<from> <to> <value>: values of one category through the maps to another",
+                run: convert,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::y2023::e5::{
+        collapse_mappings, lowest_location, traverse_mappings, traverse_preimages, traverse_ranges,
+        Mapping, MappingElement,
+    };
+
+    use crate::solution::Solution;
+
+    use super::{convert, find_seeds, parse, trace_seed, Solver};
+
+    #[test]
+    fn test_parse_all() {
+        let lines = FIXTURE
+            .lines()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>();
+        let ps = parse(lines).unwrap();
+        assert_eq!(ps.mappings.len(), 7);
+        assert_eq!(traverse_mappings(55, &ps.mappings), 86);
+        assert_eq!(traverse_mappings(79, &ps.mappings), 82);
+        assert_eq!(
+            trace_seed(&ps, &["14"]),
+            Ok("14 -> 14 -> 53 -> 49 -> 42 -> 42 -> 43 -> 43".to_string())
+        );
+    }
+
+    #[test]
+    fn test_categories() {
+        let ps = parse(FIXTURE.lines().map(|x| x.to_string()).collect()).unwrap();
+        assert_eq!(ps.categories[..3], ["seed", "soil", "fertilizer"]);
+        assert_eq!(ps.between("soil", "humidity").unwrap().len(), 5);
+        assert_eq!(ps.between("water", "water").unwrap().len(), 0);
+        assert_eq!(
+            convert(&ps, &["soil", "humidity", "81"]),
+            Ok(
+                "soil 81 -> fertilizer 81 -> water 81 -> light 74 -> temperature 78 -> humidity 78"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            ps.between("humidity", "soil").map(|_| ()),
+            Err(Error::semantic(
+                "no path from `humidity` to `soil`, the maps lead from `seed` to `location`"
+            ))
+        );
+        assert_eq!(
+            convert(&ps, &["seed", "ore", "1"]),
+            Err(Error::semantic("no `ore` category in the almanac"))
+        );
+
+        // The maps may come in any order.
+        let (head, maps) = FIXTURE.split_once("\n\n").unwrap();
+        let mut maps = maps.split("\n\n").collect::<Vec<&str>>();
+        maps.reverse();
+        let shuffled = format!("{}\n\n{}", head, maps.join("\n\n"));
+        let ps = parse(shuffled.lines().map(|x| x.to_string()).collect()).unwrap();
+        assert_eq!(ps.categories.last().unwrap(), "location");
+        assert_eq!(traverse_mappings(79, &ps.mappings), 82);
+
+        let almanac =
+            |lines: &[&str]| parse(lines.iter().map(|x| x.to_string()).collect()).map(|_| ());
+        assert_eq!(
+            almanac(&["seeds: 1", "1 2 3"]),
+            Err(Error::parse(
+                1,
+                "1 2 3",
+                "expected a `<source>-to-<destination> map:` header"
+            )
+            .on_line(2))
+        );
+        assert_eq!(
+            almanac(&["seeds: 1", "a-to-b map:", "b-to-c map:", "a-to-d map:"]),
+            Err(
+                Error::parse(1, "a", "`a` is already mapped by `a-to-b map:` on line 2").on_line(4)
+            )
+        );
+        assert_eq!(
+            almanac(&[
+                "seeds: 1",
+                "",
+                "",
+                "seed-to-soil map:",
+                "",
+                "water-to-soil map:"
+            ]),
+            Err(Error::parse(
+                10,
+                "soil",
+                "`soil` is already mapped to by `seed-to-soil map:` on line 4"
+            )
+            .on_line(6))
+        );
+        assert_eq!(
+            almanac(&["seeds: 1", "a-to-b map:", "c-to-d map:"]),
+            Err(Error::semantic(
+                "the maps form separate chains, starting from `a` and `c`"
+            ))
+        );
+        assert_eq!(
+            almanac(&["seeds: 1", "a-to-b map:", "b-to-a map:"]),
+            Err(Error::semantic(
+                "the maps form a cycle, with no category to start from"
+            ))
+        );
+        assert_eq!(
+            almanac(&[
+                "seeds: 1",
+                "a-to-b map:",
+                "c-to-d map:",
+                "d-to-c map:",
+                "x-to-a map:"
+            ]),
+            Err(Error::semantic(
+                "`c-to-d map:` on line 3 is not on the chain from `x`"
+            ))
+        );
+        assert_eq!(
+            parse(vec!["seeds: 1".to_string()])
+                .unwrap()
+                .between("seed", "location")
+                .map(|_| ()),
+            Err(Error::semantic("no `seed` category in the almanac"))
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let almanac = |lines: &[&str]| {
+            let mut source = vec!["seeds: 1".to_string(), "seed-to-soil map:".to_string()];
+            source.extend(lines.iter().map(|x| x.to_string()));
+            parse(source)
+        };
+        let ps = almanac(&["90 0 4", "50 10 2", "70 93 9"]).unwrap();
+        assert_eq!(
+            ps.mappings[0]
+                .defs()
+                .iter()
+                .map(|def| def.source_start)
+                .collect::<Vec<u64>>(),
+            vec![0, 10, 93]
+        );
+
+        assert_eq!(
+            almanac(&["50 98 2", "52 50 0"]).map(|_| ()),
+            Err(Error::parse(7, "0", "empty range in `seed-to-soil map:`").on_line(4))
+        );
+        assert_eq!(
+            almanac(&["0 18446744073709551610 5", "9 18446744073709551610 6"]).map(|_| ()),
+            Err(Error::parse(
+                3,
+                "18446744073709551610",
+                "source range ends past the largest value in `seed-to-soil map:`"
+            )
+            .on_line(4))
+        );
+        assert_eq!(
+            almanac(&["18446744073709551615 0 2"]).map(|_| ()),
+            Err(Error::parse(
+                1,
+                "18446744073709551615",
+                "destination range ends past the largest value in `seed-to-soil map:`"
+            )
+            .on_line(3))
+        );
+        assert_eq!(
+            almanac(&["52 50 48", "0 0 10", "50 97 2"]).map(|_| ()),
+            Err(Error::parse(
+                1,
+                "50 97 2",
+                "source range 97..99 overlaps 50..98 on line 3 in `seed-to-soil map:`"
+            )
+            .on_line(5))
+        );
+        assert_eq!(
+            almanac(&["50 97 2", "52 50 48"]).map(|_| ()),
+            Err(Error::parse(
+                1,
+                "52 50 48",
+                "source range 50..98 overlaps 97..99 on line 3 in `seed-to-soil map:`"
+            )
+            .on_line(4))
+        );
+        assert!(almanac(&["50 98 2", "52 50 48", "0 0 50"]).is_ok());
+    }
+
+    #[test]
+    fn test_new() {
+        let element = |line: &str| MappingElement::from_line(line.to_string()).unwrap();
+        assert_eq!(
+            Mapping::new(vec![element("100 0 10"), element("200 5 10")]).map(|_| ()),
+            Err(Error::semantic(
+                "source range 5..15 of `200 5 10` overlaps 0..10 of `100 0 10`"
+            ))
+        );
+        assert_eq!(
+            Mapping::new(vec![element("200 5 10"), element("100 0 10")]).map(|_| ()),
+            Err(Error::semantic(
+                "source range 0..10 of `100 0 10` overlaps 5..15 of `200 5 10`"
+            ))
+        );
+        assert_eq!(
+            Mapping::new(vec![element("1 2 0")]).map(|_| ()),
+            Err(Error::semantic("empty range in `1 2 0`"))
+        );
+        assert_eq!(
+            Mapping::new(vec![element("0 18446744073709551610 6")]).map(|_| ()),
+            Err(Error::semantic(
+                "source range ends past the largest value in `0 18446744073709551610 6`"
+            ))
+        );
+        assert_eq!(
+            Mapping::new(vec![element("18446744073709551610 0 6")]).map(|_| ()),
+            Err(Error::semantic(
+                "destination range ends past the largest value in `18446744073709551610 0 6`"
+            ))
+        );
+        let mapping = Mapping::new(vec![element("100 10 5"), element("200 0 10")]).unwrap();
+        assert_eq!(mapping.please_map(7), 207);
+        assert_eq!(mapping.map_range(7, 8), vec![(207, 208)]);
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut mapping = Mapping {
+            defs: vec![
+                MappingElement::from_line("50 10 2".to_string()).unwrap(),
+                MappingElement::from_line("70 93 9".to_string()).unwrap(),
+                MappingElement::from_line("90 0 4".to_string()).unwrap(),
+            ],
+        };
+        mapping.sort();
+
+        assert_eq!(
+            mapping.defs,
+            vec![
+                MappingElement {
+                    destination_start: 90,
+                    source_start: 0,
+                    range_length: 4
+                },
+                MappingElement {
+                    destination_start: 50,
+                    source_start: 10,
+                    range_length: 2
+                },
+                MappingElement {
+                    destination_start: 70,
+                    source_start: 93,
+                    range_length: 9
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        let mut mapping = Mapping {
+            defs: vec![
+                MappingElement::from_line("50 98 2".to_string()).unwrap(),
+                MappingElement::from_line("52 50 48".to_string()).unwrap(),
+            ],
+        };
+        mapping.sort();
+        assert_eq!(mapping.please_map(79), 81);
+        assert_eq!(mapping.please_map(14), 14);
+        assert_eq!(mapping.please_map(55), 57);
+        assert_eq!(mapping.please_map(13), 13);
+    }
+
+    #[test]
+    fn test_map_range() {
+        let mut mapping = Mapping {
+            defs: vec![
+                MappingElement::from_line("50 98 2".to_string()).unwrap(),
+                MappingElement::from_line("52 50 48".to_string()).unwrap(),
+            ],
+        };
+        mapping.sort();
+        let mut ranges = mapping.map_range(45, 101);
+        ranges.sort();
+        assert_eq!(ranges, vec![(45, 50), (50, 52), (52, 100), (100, 101)]);
+        assert_eq!(mapping.map_range(60, 70), vec![(62, 72)]);
+        assert_eq!(mapping.map_range(10, 20), vec![(10, 20)]);
+        assert_eq!(mapping.map_range(10, 10), vec![]);
+
+        let ps = parse(FIXTURE.lines().map(|x| x.to_string()).collect()).unwrap();
+        let mut locations = traverse_ranges(79, 93, &ps.mappings);
+        locations.sort();
+        assert_eq!(locations, vec![(46, 56), (60, 61), (82, 85)]);
+        for seed in 79..93 {
+            let location = traverse_mappings(seed, &ps.mappings);
+            assert!(locations
+                .iter()
+                .any(|&(start, end)| start <= location && location < end));
+        }
+        assert_eq!(
+            locations
+                .iter()
+                .map(|(start, end)| end - start)
+                .sum::<u64>(),
+            14
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        let solve = |seeds: &str| {
+            let source = format!("seeds: {}\n\nseed-to-location map:\n0 10 5\n", seeds);
+            Solver::part2(&Solver::parse(&source).unwrap())
+        };
+        assert_eq!(solve("12 4 3 2"), Ok(2));
+        assert_eq!(solve("12 0 3 2"), Ok(3));
+        assert_eq!(
+            solve("1 0 7 0"),
+            Err(Error::semantic("every seed range is empty"))
+        );
+        assert_eq!(
+            solve("5 1 18446744073709551610 10"),
+            Err(Error::semantic(
+                "seed range `18446744073709551610 10` ends past the largest value"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_compose() {
+        let first = Mapping::new(vec![
+            MappingElement::from_line("50 98 2".to_string()).unwrap(),
+            MappingElement::from_line("52 50 48".to_string()).unwrap(),
+        ])
+        .unwrap();
+        let second = Mapping::new(vec![
+            MappingElement::from_line("0 15 37".to_string()).unwrap(),
+            MappingElement::from_line("37 52 2".to_string()).unwrap(),
+            MappingElement::from_line("39 0 15".to_string()).unwrap(),
+        ])
+        .unwrap();
+        let composed = first.compose(&second);
+        for seed in 0..120 {
+            assert_eq!(
+                composed.please_map(seed),
+                second.please_map(first.please_map(seed)),
+                "seed {}",
+                seed
+            );
+        }
+        assert_eq!(
+            Mapping::new(vec![]).unwrap().compose(&first).defs(),
+            &[
+                MappingElement {
+                    destination_start: 52,
+                    source_start: 50,
+                    range_length: 48
+                },
+                MappingElement {
+                    destination_start: 50,
+                    source_start: 98,
+                    range_length: 2
+                }
+            ]
+        );
+        // 10..20 goes to 30..40 and back, while 30..40 is left alone then moved to 10..20.
+        let there = Mapping::new(vec![
+            MappingElement::from_line("30 10 10".to_string()).unwrap()
+        ])
+        .unwrap();
+        let back = Mapping::new(vec![
+            MappingElement::from_line("10 30 10".to_string()).unwrap()
+        ])
+        .unwrap();
+        assert_eq!(
+            there.compose(&back).defs(),
+            &[MappingElement {
+                destination_start: 10,
+                source_start: 30,
+                range_length: 10
+            }]
+        );
+
+        let ps = parse(FIXTURE.lines().map(|x| x.to_string()).collect()).unwrap();
+        let location = collapse_mappings(&ps.mappings);
+        for seed in 0..200 {
+            assert_eq!(
+                location.please_map(seed),
+                traverse_mappings(seed, &ps.mappings),
+                "seed {}",
+                seed
+            );
+        }
+        assert!(location
+            .defs()
+            .windows(2)
+            .all(|pair| pair[0].source_start + pair[0].range_length <= pair[1].source_start));
+        assert_eq!(location.please_map(u64::MAX), u64::MAX);
+
+        // Values past 2^63, which do not fit in an i64.
+        let high = Mapping::new(vec![
+            MappingElement::from_line("5 18446744073709551600 10".to_string()).unwrap(),
+            MappingElement::from_line("18446744073709551610 9223372036854775808 4".to_string())
+                .unwrap(),
+        ])
+        .unwrap();
+        assert_eq!(high.please_map(18446744073709551605), 10);
+        assert_eq!(high.please_map(18446744073709551610), 18446744073709551610);
+        assert_eq!(high.please_map(9223372036854775809), 18446744073709551611);
+        assert_eq!(high.please_map(9223372036854775812), 9223372036854775812);
+        let chain = [high, Mapping::new(vec![]).unwrap()];
+        let collapsed = collapse_mappings(&chain);
+        for value in [
+            0,
+            9223372036854775807,
+            9223372036854775808,
+            9223372036854775811,
+            18446744073709551599,
+            18446744073709551609,
+            u64::MAX,
+        ] {
+            assert_eq!(
+                collapsed.please_map(value),
+                traverse_mappings(value, &chain)
+            );
+        }
+        let ps = parse(
+            "seeds: 9223372036854775808 3\n\nseed-to-location map:\n0 1 5\n"
+                .lines()
+                .map(|x| x.to_string())
+                .collect(),
+        )
+        .unwrap();
+        assert_eq!(Solver::part1(&ps), Ok(2));
+    }
+
+    #[test]
+    fn test_preimage() {
+        let mapping = Mapping::new(vec![
+            MappingElement::from_line("50 98 2".to_string()).unwrap(),
+            MappingElement::from_line("52 50 48".to_string()).unwrap(),
+        ])
+        .unwrap();
+        assert_eq!(mapping.preimage(81, 82), vec![(79, 80)]);
+        // 51 is reached from 99, and left alone by the gap below 50.
+        assert_eq!(mapping.preimage(49, 52), vec![(49, 50), (98, 100)]);
+        assert_eq!(mapping.preimage(98, 100), vec![(96, 98)]);
+        assert_eq!(mapping.preimage(5, 5), vec![]);
+
+        let ps = parse(FIXTURE.lines().map(|x| x.to_string()).collect()).unwrap();
+        for location in 0..120 {
+            let seeds = traverse_preimages(location, location + 1, &ps.mappings);
+            for seed in 0..200 {
+                assert_eq!(
+                    seeds
+                        .iter()
+                        .any(|&(start, end)| start <= seed && seed < end),
+                    traverse_mappings(seed, &ps.mappings) == location,
+                    "seed {} to location {}",
+                    seed,
+                    location
+                );
+            }
+        }
+        assert_eq!(
+            lowest_location(&[(79, 93), (55, 68)], &ps.mappings),
+            Some(46)
+        );
+        assert_eq!(
+            lowest_location(&[(79, 80), (14, 15)], &ps.mappings),
+            Some(43)
+        );
+        assert_eq!(lowest_location(&[(5, 5)], &ps.mappings), None);
+        assert_eq!(
+            find_seeds(&ps, &["46"]),
+            Ok("46 <- 82 (listed)".to_string())
+        );
+        assert_eq!(find_seeds(&ps, &["35"]), Ok("35 <- 13".to_string()));
+    }
+
+    #[test]
+    fn test_traverse() {
+        let mut mapping_1 = Mapping {
+            defs: vec![
+                MappingElement::from_line("50 98 2".to_string()).unwrap(),
+                MappingElement::from_line("52 50 48".to_string()).unwrap(),
+            ],
+        };
+        mapping_1.sort();
+
+        let mut mapping_2 = Mapping {
+            defs: vec![
+                MappingElement::from_line("0 15 37".to_string()).unwrap(),
+                MappingElement::from_line("37 52 2".to_string()).unwrap(),
+                MappingElement::from_line("39 0 15".to_string()).unwrap(),
+            ],
+        };
+        mapping_2.sort();
+
+        let mut mapping_3 = Mapping {
+            defs: vec![
+                MappingElement::from_line("49 53 8".to_string()).unwrap(),
+                MappingElement::from_line("0 11 42".to_string()).unwrap(),
+                MappingElement::from_line("42 0 7".to_string()).unwrap(),
+                MappingElement::from_line("57 7 4".to_string()).unwrap(),
+            ],
+        };
+        mapping_3.sort();
+
+        let all_maps = vec![mapping_1, mapping_2, mapping_3];
+        assert_eq!(traverse_mappings(79, &all_maps), 81);
+        assert_eq!(traverse_mappings(14, &all_maps), 49);
+        assert_eq!(traverse_mappings(55, &all_maps), 53);
+        assert_eq!(traverse_mappings(13, &all_maps), 41);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            MappingElement::from_line("50 98 2".to_string()),
+            Ok(MappingElement {
+                destination_start: 50,
+                source_start: 98,
+                range_length: 2
+            })
+        );
+        assert_eq!(
+            MappingElement::from_line("50 98".to_string()),
+            Err(Error::parse(
+                1,
+                "50 98",
+                "expected `<destination> <source> <length>`, got 2 numbers"
+            ))
+        );
+        assert_eq!(
+            MappingElement::from_line("50 98 99999999999999999999".to_string()),
+            Err(Error::parse(
+                7,
+                "99999999999999999999",
+                "expected a number, got \"99999999999999999999\""
+            ))
+        );
+        assert_eq!(
+            parse(vec!["seeds: 1 x".to_string()]).map(|_| ()),
+            Err(Error::parse(10, "x", "expected a number, got \"x\"").on_line(1))
+        );
+        assert_eq!(
+            parse(vec![
+                "seeds: 1 2".to_string(),
+                "".to_string(),
+                "a-to-b map:".to_string(),
+                "1 2".to_string()
+            ])
+            .map(|_| ()),
+            Err(Error::parse(
+                1,
+                "1 2",
+                "expected `<destination> <source> <length>`, got 2 numbers"
+            )
+            .on_line(4))
+        );
+        assert_eq!(
+            parse(vec!["a-to-b map:".to_string(), "1 2 3".to_string()]).map(|_| ()),
+            Err(Error::semantic("no seeds listed in the almanac"))
+        );
+    }
+
+    static FIXTURE: &str = include_str!("../../../fixtures/2023/e5/example.txt");
+}
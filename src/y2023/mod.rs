@@ -0,0 +1,17 @@
+//! Advent of Code 2023.
+
+pub mod e1;
+pub mod e2;
+pub mod e3;
+pub mod e4;
+pub mod e5;
+
+use crate::solution::Day;
+
+pub static DAYS: &[Day] = &[
+    Day::new::<e1::Solver>(2023, 1),
+    Day::new::<e2::Solver>(2023, 2),
+    Day::new::<e3::Solver>(2023, 3),
+    Day::new::<e4::Solver>(2023, 4),
+    Day::new::<e5::Solver>(2023, 5),
+];
@@ -0,0 +1,32 @@
+//! Runs every example under `fixtures/` against its day, so that adding an example only takes
+//! dropping its input and `.answers` sidecar in `fixtures/<year>/eN/`.
+
+use std::path::Path;
+
+use aoc2023::{find_day, fixtures};
+
+#[test]
+fn examples() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(fixtures::FIXTURES_DIR);
+    let found = fixtures::discover(&dir).unwrap();
+    assert!(!found.is_empty(), "no example found in {}", dir.display());
+
+    let mut failures = vec![];
+    for fixture in &found {
+        let checks = find_day(fixture.year, fixture.day)
+            .and_then(|day| fixture.check(day).map_err(|err| err.to_string()));
+        match checks {
+            Ok(checks) => failures.extend(checks.iter().filter(|c| !c.passed()).map(|c| {
+                format!(
+                    "{} part {}: expected {}, got {}",
+                    fixture.path.display(),
+                    c.part,
+                    c.expected,
+                    c.actual
+                )
+            })),
+            Err(message) => failures.push(format!("{}: {}", fixture.path.display(), message)),
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,92 @@
+//! Solves days through the HTTP service, with a client as a teammate would use it.
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+use aoc2023::serve;
+
+/// Serves on a free local port, accepting bodies of up to `max_body` bytes.
+fn start(max_body: usize) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    thread::spawn(move || serve::serve(listener, max_body));
+    address
+}
+
+fn post(address: &str, path: &str, body: &str) -> (u16, String) {
+    match ureq::post(&format!("http://{}{}", address, path)).send_string(body) {
+        Ok(response) => (response.status(), response.into_string().unwrap()),
+        Err(ureq::Error::Status(status, response)) => (status, response.into_string().unwrap()),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+const EXAMPLE: &str = include_str!("../fixtures/2023/e5/example.txt");
+
+#[test]
+fn solve_over_http() {
+    let address = start(serve::DEFAULT_MAX_BODY);
+
+    let (status, body) = post(&address, "/2023/day/5?part=2", EXAMPLE);
+    assert_eq!(status, 200);
+    assert!(body.starts_with(
+        r#"{"results":[{"year":2023,"day":5,"part":2,"answer":"46","timings":{"parse_ns":"#
+    ));
+    assert!(
+        body.ends_with(r#""status":"ok","error":null}]}"#),
+        "{}",
+        body
+    );
+
+    let (status, body) = post(&address, "/2023/day/5", EXAMPLE);
+    assert_eq!(status, 200);
+    assert!(body.contains(r#""part":1,"answer":"35""#), "{}", body);
+    assert!(body.contains(r#""part":2,"answer":"46""#), "{}", body);
+
+    let (status, body) = post(&address, "/2023/day/5", "seeds: 1 x\n");
+    assert_eq!(status, 422);
+    assert!(
+        body.contains(r#""status":"error","error":"<body>:1:"#),
+        "{}",
+        body
+    );
+
+    let (status, body) = post(&address, "/2023/day/25", EXAMPLE);
+    assert_eq!(status, 404);
+    assert!(
+        body.starts_with(r#"{"error":"unknown day `e25` of 2023"#),
+        "{}",
+        body
+    );
+
+    let response = ureq::get(&format!("http://{}/", address)).call().unwrap();
+    assert!(response
+        .into_string()
+        .unwrap()
+        .starts_with(r#"{"days":[{"year":2023,"day":1},"#));
+}
+
+#[test]
+fn limit_requests() {
+    let address = start(64);
+
+    let (status, body) = post(&address, "/2023/day/5", EXAMPLE);
+    assert_eq!(status, 413);
+    assert_eq!(body, r#"{"error":"inputs are limited to 64 bytes"}"#);
+    let small = "seeds: 1\n\nseed-to-location map:\n5 1 1\n";
+    assert_eq!(post(&address, "/2023/day/5?part=1", small).0, 200);
+
+    let raw = |request: String| {
+        let mut stream = TcpStream::connect(&address).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+    assert!(raw("POST /2023/day/5 HTTP/1.1\r\n\r\n".to_string())
+        .starts_with("HTTP/1.1 411 Length Required\r\n"));
+    let header = format!("X-Padding: {}\r\n", "a".repeat(10 << 10));
+    assert!(raw(format!("GET / HTTP/1.1\r\n{}\r\n", header))
+        .starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"));
+}